@@ -0,0 +1,206 @@
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Set this variable to bypass the cache and always run the solver.
+pub const NO_CACHE_VAR: &str = "AOC_NO_CACHE";
+
+/// 64-bit FNV-1a, fed through `Write`. Unlike `DefaultHasher` its values do
+/// not change between Rust releases, so cache keys survive toolchain upgrades.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+}
+
+impl Write for Fnv1a {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Hash of the file content, read through a fixed buffer.
+pub fn content_hash(path: impl AsRef<Path>) -> io::Result<u64> {
+    let mut hasher = Fnv1a(Fnv1a::OFFSET_BASIS);
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.0)
+}
+
+/// Version of the running solver: a hash of its own executable, so that any
+/// rebuild of the solver crate (or of its dependencies) invalidates cached answers.
+pub fn solver_version() -> io::Result<String> {
+    Ok(format!("{:016x}", content_hash(env::current_exe()?)?))
+}
+
+/// On-disk answers keyed by day, part and input content hash.
+/// Every entry records the solver version that produced it and is ignored
+/// (and later overwritten) when the version differs.
+#[derive(Debug)]
+pub struct AnswerCache {
+    root: PathBuf,
+    version: String,
+}
+
+impl AnswerCache {
+    pub fn new(root: impl Into<PathBuf>, version: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            version: version.into(),
+        }
+    }
+
+    pub fn open() -> io::Result<Self> {
+        let mut root = crate::parent_of_manifest();
+        root.push("target");
+        root.push("answers");
+        Ok(Self::new(root, solver_version()?))
+    }
+
+    fn entry_path(&self, day: &str, part: &str, input_hash: u64) -> PathBuf {
+        let mut path = self.root.clone();
+        path.push(format!("day{day}"));
+        path.push(format!("part{part}"));
+        path.push(format!("{input_hash:016x}"));
+        path
+    }
+
+    pub fn get(&self, day: &str, part: &str, input_hash: u64) -> Option<String> {
+        let entry = fs::read_to_string(self.entry_path(day, part, input_hash)).ok()?;
+        let (version, answer) = entry.split_once('\n')?;
+        (version == self.version).then(|| answer.to_string())
+    }
+
+    /// Stores the answer. The entry is written to a temporary file next to it
+    /// and renamed into place, so `get` never sees a partial entry.
+    pub fn put(&self, day: &str, part: &str, input_hash: u64, answer: &str) -> io::Result<()> {
+        let path = self.entry_path(day, part, input_hash);
+        fs::create_dir_all(path.parent().unwrap())?;
+        let temp = path.with_extension(format!("tmp{}", process::id()));
+        fs::write(&temp, format!("{}\n{answer}", self.version))
+            .and_then(|()| fs::rename(&temp, &path))
+            .inspect_err(|_| {
+                let _ = fs::remove_file(&temp);
+            })
+    }
+}
+
+/// Answer for `day`/`part` on `input`, taken from the cache when possible.
+/// Cache failures are not fatal: they are reported and the solver is run.
+/// The cache is bypassed when allocations are profiled.
+pub fn cached<T: Display>(
+    day: &str,
+    part: &str,
+    input: impl AsRef<Path>,
+    solve: impl FnOnce() -> T,
+) -> String {
+    if cfg!(feature = "alloc-profile") || env::var_os(NO_CACHE_VAR).is_some() {
+        return solve().to_string();
+    }
+    let (cache, input_hash) =
+        match AnswerCache::open().and_then(|cache| Ok((cache, content_hash(input)?))) {
+            Ok(found) => found,
+            Err(err) => {
+                eprintln!("answer cache: {err}");
+                return solve().to_string();
+            }
+        };
+    if let Some(answer) = cache.get(day, part, input_hash) {
+        return answer;
+    }
+    let answer = solve().to_string();
+    if let Err(err) = cache.put(day, part, input_hash, &answer) {
+        eprintln!("answer cache: {err}");
+    }
+    answer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_input;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc24-cache-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    #[test]
+    fn test_get_put() {
+        let root = temp_root("get_put");
+        let input_hash = content_hash(test_input("01", "")).unwrap();
+
+        let cache = AnswerCache::new(&root, "v1");
+        assert_eq!(cache.get("01", "1", input_hash), None);
+        cache.put("01", "1", input_hash, "11").unwrap();
+        assert_eq!(cache.get("01", "1", input_hash), Some("11".to_string()));
+        assert_eq!(cache.get("01", "2", input_hash), None);
+        assert_eq!(cache.get("02", "1", input_hash), None);
+        assert_eq!(cache.get("01", "1", input_hash + 1), None);
+
+        let cache = AnswerCache::new(&root, "v2");
+        assert_eq!(cache.get("01", "1", input_hash), None);
+        cache.put("01", "1", input_hash, "31").unwrap();
+        assert_eq!(cache.get("01", "1", input_hash), Some("31".to_string()));
+        assert_eq!(
+            AnswerCache::new(&root, "v1").get("01", "1", input_hash),
+            None
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(test_input("03", "1")).unwrap(),
+            content_hash(test_input("03", "1")).unwrap()
+        );
+        assert_ne!(
+            content_hash(test_input("03", "1")).unwrap(),
+            content_hash(test_input("03", "2")).unwrap()
+        );
+
+        let root = temp_root("content_hash");
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("input");
+        fs::write(&path, "").unwrap();
+        assert_eq!(content_hash(&path).unwrap(), 0xcbf29ce484222325);
+        fs::write(&path, "a").unwrap();
+        assert_eq!(content_hash(&path).unwrap(), 0xaf63dc4c8601ec8c);
+        // Longer than the copy buffer.
+        let long = vec![b'x'; 100_000];
+        fs::write(&path, &long).unwrap();
+        let mut hasher = Fnv1a(Fnv1a::OFFSET_BASIS);
+        hasher.write_all(&long).unwrap();
+        assert_eq!(content_hash(&path).unwrap(), hasher.0);
+        assert!(content_hash(root.join("missing")).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_put_leaves_no_temp_files() {
+        let root = temp_root("put");
+        let cache = AnswerCache::new(&root, "v1");
+        cache.put("01", "1", 7, "11").unwrap();
+        cache.put("01", "1", 7, "12").unwrap();
+        assert_eq!(cache.get("01", "1", 7), Some("12".to_string()));
+        let entries: Vec<_> = fs::read_dir(root.join("day01").join("part1"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(entries, [format!("{:016x}", 7)]);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,3 +1,9 @@
+mod cache;
+pub mod profile;
+
+pub use cache::{cached, content_hash, solver_version, AnswerCache, NO_CACHE_VAR};
+pub use profile::profile;
+
 use std::path::{Path, PathBuf};
 
 fn common_input(first_dir: impl AsRef<Path>, day: &str, task: &str) -> PathBuf {
@@ -0,0 +1,142 @@
+//! Allocation profiling, enabled with the `alloc-profile` feature of this crate
+//! (e.g. `cargo run -p day09_1 --features common/alloc-profile`). The feature
+//! installs a counting global allocator and makes [`profile`] report the cost
+//! of every profiled step to stderr. Profiled runs always bypass the answer cache.
+
+#[cfg(feature = "alloc-profile")]
+pub use counting::{measure, AllocStats, CountingAllocator};
+
+#[cfg(feature = "alloc-profile")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::fmt::{Display, Formatter};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// [`System`] allocator keeping global allocation counters.
+    pub struct CountingAllocator;
+
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Allocations made while running a step. `peak` is the highest amount of
+    /// live memory above what was already allocated when the step started.
+    #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+    pub struct AllocStats {
+        pub allocations: usize,
+        pub bytes: usize,
+        pub peak: usize,
+    }
+
+    impl Display for AllocStats {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "{} allocations, {} bytes, peak {} bytes",
+                self.allocations, self.bytes, self.peak
+            )
+        }
+    }
+
+    /// Runs `f` and counts its allocations. Resets the global peak, so nested
+    /// measurements report the outer peak only from the end of the inner one.
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let allocated = ALLOCATED.load(Ordering::Relaxed);
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        let result = f();
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+        };
+        (result, stats)
+    }
+}
+
+/// Runs `f` as the profiled step `label`.
+#[cfg(feature = "alloc-profile")]
+pub fn profile<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let (result, stats) = measure(f);
+    eprintln!("{label}: {stats}");
+    result
+}
+
+/// Runs `f` as the profiled step `label`.
+#[cfg(not(feature = "alloc-profile"))]
+pub fn profile<T>(_label: &str, f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+#[cfg(all(test, feature = "alloc-profile"))]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    // Counters are global and other tests run concurrently, so only lower
+    // bounds of the monotonic counters can be checked.
+    #[test]
+    fn test_measure() {
+        let (v, stats) = measure(|| black_box(vec![0u8; 1000]));
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 1000);
+        drop(v);
+
+        let (_, stats) = measure(|| {
+            for _ in 0..3 {
+                black_box(vec![0u64; 100]);
+            }
+        });
+        assert!(stats.allocations >= 3);
+        assert!(stats.bytes >= 2400);
+    }
+
+    #[test]
+    fn test_profile() {
+        assert_eq!(profile("test", || black_box(vec![1, 2, 3])), vec![1, 2, 3]);
+    }
+}
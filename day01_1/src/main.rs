@@ -14,29 +14,74 @@
   limitations under the License.
 */
 
-use common::input;
-use day01_lib::{load_input, DAY};
-use std::iter;
+use common::{cached, input, profile};
+use day01_lib::{external_total_distance, load_input, total_distance, Report, DAY};
+use std::env;
+use std::fs::File;
+use std::io::BufReader;
+use std::process::ExitCode;
 
-fn total_distance(mut a: Vec<u32>, mut b: Vec<u32>) -> u32 {
-    a.sort_unstable();
-    b.sort_unstable();
-    iter::zip(a, b).map(|(a, b)| a.abs_diff(b)).sum()
-}
-
-fn main() {
-    let (a, b) = load_input(input(DAY, ""));
-    println!("{}", total_distance(a, b));
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use common::test_input;
+const USAGE: &str = "usage: day01_1 [--external CHUNK_LEN [PATH] | --report [TOP [PATH]]]";
+const REPORT_TOP: usize = 10;
+/// IDs of the external sort mode, the type of the puzzle input. Inputs with
+/// negative or larger IDs are rejected naming the offending line.
+type ExternalId = u32;
 
-    #[test]
-    fn test_total_distance() {
-        let (a, b) = load_input(test_input(DAY, ""));
-        assert_eq!(total_distance(a, b), 11);
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => {
+            let path = input(DAY, "");
+            println!(
+                "{}",
+                cached(DAY, "1", &path, || {
+                    let (a, b) = profile("parse", || load_input(&path));
+                    profile("part 1", || {
+                        total_distance(a, b).expect("columns have the same length")
+                    })
+                })
+            );
+        }
+        [flag, chunk_len, path @ ..] if flag == "--external" && path.len() <= 1 => {
+            let Some(chunk_len) = chunk_len.parse().ok().filter(|&n| n > 0) else {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            };
+            let path = path.first().map_or_else(|| input(DAY, ""), Into::into);
+            let distance = File::open(path).and_then(|file| {
+                external_total_distance::<ExternalId>(
+                    BufReader::new(file),
+                    chunk_len,
+                    env::temp_dir(),
+                )
+            });
+            match distance {
+                Ok(distance) => println!("{distance}"),
+                Err(err) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        [flag, args @ ..] if flag == "--report" && args.len() <= 2 => {
+            let Some(top) = args
+                .first()
+                .map_or(Some(REPORT_TOP), |top| top.parse().ok())
+            else {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            };
+            let path = args.get(1).map_or_else(|| input(DAY, ""), Into::into);
+            let (a, b) = load_input(path);
+            print!(
+                "{}",
+                Report::new(a, b, top).expect("columns have the same length")
+            );
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
     }
+    ExitCode::SUCCESS
 }
@@ -14,33 +14,43 @@
   limitations under the License.
 */
 
-use common::input;
-use day01_lib::{load_input, DAY};
-use std::collections::HashMap;
-
-fn similarity_score(a: impl IntoIterator<Item = u32>, b: impl IntoIterator<Item = u32>) -> u32 {
-    let b_occurrences = b.into_iter().fold(HashMap::new(), |mut acc, b| {
-        acc.entry(b).and_modify(|count| *count += 1).or_insert(1);
-        acc
-    });
-    a.into_iter()
-        .filter_map(|a| b_occurrences.get(&a).map(|b| a * b))
-        .sum()
-}
-
-fn main() {
-    let (a, b) = load_input(input(DAY, ""));
-    println!("{}", similarity_score(a, b));
+use common::{cached, input, profile};
+use day01_lib::{load_input, similarity_score, MetricKind, DAY};
+use std::env;
+use std::process::ExitCode;
+use strum::IntoEnumIterator;
+
+fn usage() -> String {
+    let metrics: Vec<_> = MetricKind::iter().map(|kind| kind.to_string()).collect();
+    format!("usage: day01_2 [--metric {} [PATH]]", metrics.join("|"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use common::test_input;
-
-    #[test]
-    fn test_similarity_score() {
-        let (a, b) = load_input(test_input(DAY, ""));
-        assert_eq!(similarity_score(a, b), 31);
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => {
+            let path = input(DAY, "");
+            println!(
+                "{}",
+                cached(DAY, "2", &path, || {
+                    let (a, b) = profile("parse", || load_input(&path));
+                    profile("part 2", || similarity_score(a, b))
+                })
+            );
+        }
+        [flag, metric, path @ ..] if flag == "--metric" && path.len() <= 1 => {
+            let Ok(metric) = metric.parse::<MetricKind>() else {
+                eprintln!("{}", usage());
+                return ExitCode::FAILURE;
+            };
+            let path = path.first().map_or_else(|| input(DAY, ""), Into::into);
+            let (a, b) = load_input(path);
+            println!("{}", metric.measure(a, b));
+        }
+        _ => {
+            eprintln!("{}", usage());
+            return ExitCode::FAILURE;
+        }
     }
+    ExitCode::SUCCESS
 }
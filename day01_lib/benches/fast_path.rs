@@ -0,0 +1,69 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day01_lib::{
+    comparison_total_distance, hashed_similarity_score, parse_input, similarity_score,
+    total_distance,
+};
+use generator::{day01, rng};
+
+fn lists(pairs: usize) -> (Vec<u32>, Vec<u32>) {
+    let mut out = vec![];
+    day01::generate(&mut out, &mut rng(1), pairs).unwrap();
+    parse_input(out.as_slice()).unwrap()
+}
+
+fn bench_fast_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("total_distance");
+    for pairs in [1_000, 100_000] {
+        let input = lists(pairs);
+        group.bench_with_input(BenchmarkId::new("radix", pairs), &input, |bench, input| {
+            bench.iter_batched(
+                || input.clone(),
+                |(a, b)| total_distance(a, b),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(
+            BenchmarkId::new("comparison", pairs),
+            &input,
+            |bench, input| {
+                bench.iter_batched(
+                    || input.clone(),
+                    |(a, b)| comparison_total_distance(a, b),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("similarity_score");
+    for pairs in [1_000, 100_000] {
+        let (a, b) = lists(pairs);
+        group.bench_with_input(BenchmarkId::new("dense", pairs), &pairs, |bench, _| {
+            bench.iter(|| similarity_score(a.iter().copied(), b.iter().copied()))
+        });
+        group.bench_with_input(BenchmarkId::new("hashed", pairs), &pairs, |bench, _| {
+            bench.iter(|| hashed_similarity_score(a.iter().copied(), b.iter().copied()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_fast_path);
+criterion_main!(benches);
@@ -0,0 +1,156 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+//! Fast paths for IDs spanning a bounded range, such as the five-digit IDs of
+//! the puzzle: LSD radix sort and a dense count array instead of comparison
+//! sort and hashing.
+
+use crate::LocationId;
+use std::mem;
+
+const RADIX_BITS: u32 = 8;
+/// Radix sort needs at most four passes below this range.
+const MAX_RADIX_RANGE: usize = u32::MAX as usize;
+/// Below this length the comparison sort wins.
+const MIN_RADIX_LEN: usize = 64;
+/// Largest dense count array, in elements.
+const MAX_DENSE_RANGE: usize = 1 << 20;
+/// Largest count array per counted ID: sparser IDs are cheaper to hash.
+const MAX_DENSE_RANGE_PER_ID: usize = 16;
+
+/// Smallest ID and the distance from it to the largest one.
+fn bounds<T: LocationId>(ids: &[T]) -> Option<(T, usize)> {
+    let min = *ids.iter().min()?;
+    let max = *ids.iter().max()?;
+    Some((min, max.offset_from(min)?))
+}
+
+fn radix_sort<T: LocationId>(mut ids: Vec<T>, min: T, range: usize) -> Vec<T> {
+    let digits = (usize::BITS - range.leading_zeros()).div_ceil(RADIX_BITS);
+    let mut sorted = vec![min; ids.len()];
+    for shift in (0..digits).map(|digit| digit * RADIX_BITS) {
+        let digit = |id: T| (id.offset_from(min).unwrap() >> shift) & ((1 << RADIX_BITS) - 1);
+        let mut starts = [0; 1 << RADIX_BITS];
+        ids.iter().for_each(|&id| starts[digit(id)] += 1);
+        starts.iter_mut().fold(0, |start, count| {
+            let next = start + *count;
+            *count = start;
+            next
+        });
+        for &id in &ids {
+            let start = &mut starts[digit(id)];
+            sorted[*start] = id;
+            *start += 1;
+        }
+        mem::swap(&mut ids, &mut sorted);
+    }
+    ids
+}
+
+/// Sorts `ids`, with radix sort when their range is bounded.
+pub(crate) fn sort_ids<T: LocationId>(mut ids: Vec<T>) -> Vec<T> {
+    match bounds(&ids) {
+        Some((min, range)) if ids.len() >= MIN_RADIX_LEN && range <= MAX_RADIX_RANGE => {
+            radix_sort(ids, min, range)
+        }
+        _ => {
+            ids.sort_unstable();
+            ids
+        }
+    }
+}
+
+/// Similarity score counting `b` in a dense array, or `None` if its range is
+/// too wide for one, in total or relative to its length.
+pub(crate) fn dense_similarity_score<T: LocationId>(
+    a: impl IntoIterator<Item = T>,
+    b: &[T],
+) -> Option<i128> {
+    let (min, range) = bounds(b).filter(|&(_, range)| {
+        range < MAX_DENSE_RANGE && range / MAX_DENSE_RANGE_PER_ID < b.len()
+    })?;
+    let mut counts = vec![0usize; range + 1];
+    b.iter()
+        .for_each(|id| counts[id.offset_from(min).unwrap()] += 1);
+    Some(
+        a.into_iter()
+            .filter(|&id| id >= min)
+            .filter_map(|id| {
+                let count = *counts.get(id.offset_from(min)?)?;
+                (count > 0).then(|| id.similarity(count))
+            })
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hashed_similarity_score, load_input, parse_input, DAY};
+    use common::test_input;
+    use generator::rng;
+
+    fn sorted<T: LocationId>(mut ids: Vec<T>) -> Vec<T> {
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_radix_sort() {
+        let ids: Vec<i64> = (0..500).map(|i| (i * 7919) % 1013 - 300).collect();
+        let (min, range) = bounds(&ids).unwrap();
+        assert_eq!(radix_sort(ids.clone(), min, range), sorted(ids));
+
+        let ids: Vec<u32> = (0..300u32).map(|i| i.wrapping_mul(2654435761)).collect();
+        let (min, range) = bounds(&ids).unwrap();
+        assert_eq!(radix_sort(ids.clone(), min, range), sorted(ids));
+
+        let ids = vec![i8::MIN, i8::MAX, 0, -1, 1, i8::MIN];
+        let (min, range) = bounds(&ids).unwrap();
+        assert_eq!(radix_sort(ids.clone(), min, range), sorted(ids));
+    }
+
+    #[test]
+    fn test_sort_ids() {
+        assert_eq!(sort_ids(Vec::<u8>::new()), vec![]);
+        let ids: Vec<i64> = (0..100).map(|i| i64::MAX - i * (i % 3)).collect();
+        assert_eq!(sort_ids(ids.clone()), sorted(ids));
+        let ids: Vec<u16> = (0..100).rev().collect();
+        assert_eq!(sort_ids(ids.clone()), sorted(ids));
+    }
+
+    #[test]
+    fn test_dense_similarity_score() {
+        assert_eq!(
+            dense_similarity_score([-3i64, 4, -5, 40], &[-3, -3, 4, 5]),
+            Some(-2)
+        );
+        assert_eq!(dense_similarity_score([1u32], &[]), None);
+        assert_eq!(dense_similarity_score([1u64], &[0, u64::MAX]), None);
+        assert_eq!(dense_similarity_score([1u32], &[0, 100_000]), None);
+
+        let (a, b) = load_input(test_input(DAY, ""));
+        assert_eq!(dense_similarity_score(a, &b), Some(31));
+
+        let mut input = vec![];
+        generator::day01::generate(&mut input, &mut rng(35), 100_000).unwrap();
+        let (a, b) = parse_input(input.as_slice()).unwrap();
+        assert_eq!(
+            dense_similarity_score(a.iter().copied(), &b),
+            Some(hashed_similarity_score(a, b))
+        );
+    }
+}
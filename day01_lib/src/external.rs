@@ -0,0 +1,291 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use crate::{parse_line, LocationId};
+use std::any;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, ErrorKind, Lines, Write};
+use std::iter;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static RUNS_DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+/// Most runs merged at once. Both columns are merged side by side, so twice as
+/// many files are open at the end.
+const MAX_FAN_IN: usize = 64;
+
+/// Sorted runs of both columns spilled to a private directory, removed on drop.
+struct Runs {
+    dir: PathBuf,
+    /// Run files written so far, used to name the next one.
+    written: usize,
+    columns: [Vec<PathBuf>; 2],
+}
+
+impl Runs {
+    fn new(tmp_dir: &Path) -> io::Result<Self> {
+        let dir = tmp_dir.join(format!(
+            "aoc24-day01-runs-{}-{}",
+            process::id(),
+            RUNS_DIR_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            written: 0,
+            columns: [vec![], vec![]],
+        })
+    }
+
+    /// Writes sorted `ids` to a new run file of `column`.
+    fn write_run<T: Display>(
+        &mut self,
+        column: usize,
+        ids: impl IntoIterator<Item = io::Result<T>>,
+    ) -> io::Result<()> {
+        let path = self.dir.join(self.written.to_string());
+        self.written += 1;
+        let mut out = BufWriter::new(File::create(&path)?);
+        for id in ids {
+            writeln!(out, "{}", id?)?;
+        }
+        out.flush()?;
+        self.columns[column].push(path);
+        Ok(())
+    }
+
+    fn spill<T: Ord + Display>(&mut self, chunk: &mut [Vec<T>; 2]) -> io::Result<()> {
+        for (column, ids) in chunk.iter_mut().enumerate() {
+            ids.sort_unstable();
+            self.write_run(column, ids.drain(..).map(Ok))?;
+        }
+        Ok(())
+    }
+
+    /// Merges the runs of `column`, first in passes of `fan_in` runs until at
+    /// most `fan_in` are left, so that never more files are open at once.
+    fn merge<T: Ord + FromStr + Display>(
+        &mut self,
+        column: usize,
+        fan_in: usize,
+    ) -> io::Result<Merge<T>> {
+        debug_assert!(fan_in > 1, "merge passes must reduce the run count");
+        while self.columns[column].len() > fan_in {
+            let runs = mem::take(&mut self.columns[column]);
+            for group in runs.chunks(fan_in) {
+                self.write_run(column, Merge::<T>::open(group)?)?;
+                group.iter().try_for_each(fs::remove_file)?;
+            }
+        }
+        Merge::open(&self.columns[column])
+    }
+}
+
+impl Drop for Runs {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn read_id<T: FromStr>(run: &mut Lines<BufReader<File>>) -> io::Result<Option<T>> {
+    run.next()
+        .map(|line| {
+            line?
+                .parse()
+                .map_err(|_| io::Error::new(ErrorKind::InvalidData, "corrupted run"))
+        })
+        .transpose()
+}
+
+/// K-way merge of sorted runs.
+struct Merge<T> {
+    runs: Vec<Lines<BufReader<File>>>,
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+}
+
+impl<T: Ord + FromStr> Merge<T> {
+    fn open(paths: &[PathBuf]) -> io::Result<Self> {
+        let runs = paths
+            .iter()
+            .map(|path| Ok(BufReader::new(File::open(path)?).lines()))
+            .collect::<io::Result<Vec<_>>>()?;
+        Self::new(runs)
+    }
+
+    fn new(mut runs: Vec<Lines<BufReader<File>>>) -> io::Result<Self> {
+        let mut heap = BinaryHeap::with_capacity(runs.len());
+        for (i, run) in runs.iter_mut().enumerate() {
+            if let Some(id) = read_id(run)? {
+                heap.push(Reverse((id, i)));
+            }
+        }
+        Ok(Self { runs, heap })
+    }
+}
+
+impl<T: Ord + FromStr> Iterator for Merge<T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((id, run)) = self.heap.pop()?;
+        match read_id(&mut self.runs[run]) {
+            Ok(Some(next)) => self.heap.push(Reverse((next, run))),
+            Ok(None) => {}
+            Err(err) => return Some(Err(err)),
+        }
+        Some(Ok(id))
+    }
+}
+
+/// Same as [`crate::total_distance`] over two columns read from `reader`, but
+/// holding at most `chunk_len` pairs in memory: sorted chunks are spilled to
+/// files under `tmp_dir` and merged back column by column. IDs that do not
+/// parse as `T`, e.g. out of its range, are reported with their line.
+pub fn external_total_distance<T>(
+    reader: impl BufRead,
+    chunk_len: usize,
+    tmp_dir: impl AsRef<Path>,
+) -> io::Result<u128>
+where
+    T: LocationId + Display,
+{
+    total_distance_with_fan_in::<T>(reader, chunk_len, tmp_dir.as_ref(), MAX_FAN_IN)
+}
+
+fn total_distance_with_fan_in<T>(
+    reader: impl BufRead,
+    chunk_len: usize,
+    tmp_dir: &Path,
+    fan_in: usize,
+) -> io::Result<u128>
+where
+    T: LocationId + Display,
+{
+    assert!(chunk_len > 0, "chunk must hold at least one pair");
+    let mut runs = Runs::new(tmp_dir)?;
+    let mut chunk = [Vec::with_capacity(chunk_len), Vec::with_capacity(chunk_len)];
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let [a, b] = parse_line(line.as_str())
+            .and_then(|row: Vec<T>| <[T; 2]>::try_from(row).ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "invalid line {}: {line:?}, expected two IDs of type {}",
+                        i + 1,
+                        any::type_name::<T>()
+                    ),
+                )
+            })?;
+        chunk[0].push(a);
+        chunk[1].push(b);
+        if chunk[0].len() == chunk_len {
+            runs.spill(&mut chunk)?;
+        }
+    }
+    if !chunk[0].is_empty() {
+        runs.spill(&mut chunk)?;
+    }
+    let a = runs.merge::<T>(0, fan_in)?;
+    let b = runs.merge::<T>(1, fan_in)?;
+    iter::zip(a, b).map(|(a, b)| Ok(a?.distance(b?))).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_input, parse_input, total_distance, DAY};
+    use common::test_input;
+    use proptest::prelude::*;
+    use std::env;
+
+    #[test]
+    fn test_external_total_distance() {
+        let input = fs::read(test_input(DAY, "")).unwrap();
+        let (a, b) = load_input(test_input(DAY, ""));
+        let expected = total_distance(a, b).unwrap();
+        for chunk_len in [1, 2, 4, 6, 100] {
+            assert_eq!(
+                external_total_distance::<u32>(&input[..], chunk_len, env::temp_dir()).unwrap(),
+                expected,
+                "chunk_len = {chunk_len}"
+            );
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn prop_external_total_distance(
+            pairs in prop::collection::vec((-(1i64 << 40)..1 << 40, -(1i64 << 40)..1 << 40), 0..500),
+            chunk_len in 1usize..100,
+            fan_in in 2usize..8,
+        ) {
+            let input: String = pairs.iter().map(|(a, b)| format!("{a} {b}\n")).collect();
+            let (a, b) = pairs.into_iter().unzip();
+            prop_assert_eq!(
+                total_distance_with_fan_in::<i64>(input.as_bytes(), chunk_len, &env::temp_dir(), fan_in)
+                    .unwrap(),
+                total_distance(a, b).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_passes() {
+        let input: String = (0..100)
+            .map(|i| format!("{} {}\n", (i * 37) % 101, i))
+            .collect();
+        let (a, b) = parse_input(input.as_bytes()).unwrap();
+        let expected = total_distance(a, b).unwrap();
+        for fan_in in [2, 3, 7, 100] {
+            assert_eq!(
+                total_distance_with_fan_in::<u32>(input.as_bytes(), 1, &env::temp_dir(), fan_in)
+                    .unwrap(),
+                expected,
+                "fan_in = {fan_in}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_external_total_distance_invalid() {
+        assert!(external_total_distance::<u32>(&b"1 2\n3\n"[..], 1, env::temp_dir()).is_err());
+        let err = external_total_distance::<u32>(&b"1 2\n1 4294967296\n"[..], 1, env::temp_dir())
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "invalid line 2: \"1 4294967296\", expected two IDs of type u32"
+        );
+        assert!(external_total_distance::<u32>(&b"-1 2\n"[..], 1, env::temp_dir()).is_err());
+        assert_eq!(
+            external_total_distance::<u32>(&b""[..], 1, env::temp_dir()).unwrap(),
+            0
+        );
+    }
+}
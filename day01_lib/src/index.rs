@@ -0,0 +1,440 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use crate::LocationId;
+use std::collections::HashMap;
+
+/// List of a [`SimilarityIndex`]: `Left` is scored against `Right`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum List {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Scores {
+    pub similarity_score: i128,
+    /// `None` while the lists have different lengths.
+    pub total_distance: Option<u128>,
+}
+
+/// Run of consecutive points of a [`CdfDiff`] with a lazy offset added to all
+/// its differences. Weights of the points are kept in a histogram by raw
+/// difference, so that shifting the whole block updates its sum in O(1).
+#[derive(Debug, Default)]
+struct Block {
+    values: Vec<i128>,
+    diffs: Vec<i64>,
+    widths: Vec<i128>,
+    offset: i64,
+    histogram: HashMap<i64, i128>,
+    width: i128,
+    nonneg_width: i128,
+    abs_sum: i128,
+}
+
+impl Block {
+    fn contribute(&mut self, j: usize, sign: i128) {
+        let (diff, width) = (self.diffs[j], self.widths[j] * sign);
+        let shifted = diff + self.offset;
+        let entry = self.histogram.entry(diff).or_default();
+        *entry += width;
+        if *entry == 0 {
+            self.histogram.remove(&diff);
+        }
+        self.width += width;
+        if shifted >= 0 {
+            self.nonneg_width += width;
+        }
+        self.abs_sum += shifted.unsigned_abs() as i128 * width;
+    }
+
+    fn add_point(&mut self, j: usize, delta: i64) {
+        self.contribute(j, -1);
+        self.diffs[j] += delta;
+        self.contribute(j, 1);
+    }
+
+    fn set_width(&mut self, j: usize, width: i128) {
+        self.contribute(j, -1);
+        self.widths[j] = width;
+        self.contribute(j, 1);
+    }
+
+    fn add_all(&mut self, delta: i64) {
+        let at = |block: &Self, shifted: i64| {
+            block
+                .histogram
+                .get(&(shifted - block.offset))
+                .copied()
+                .unwrap_or_default()
+        };
+        if delta > 0 {
+            self.abs_sum += self.nonneg_width - (self.width - self.nonneg_width);
+            self.nonneg_width += at(self, -1);
+        } else {
+            let positive_width = self.nonneg_width - at(self, 0);
+            self.abs_sum += (self.width - positive_width) - positive_width;
+            self.nonneg_width = positive_width;
+        }
+        self.offset += delta;
+    }
+
+    fn diff(&self, j: usize) -> i64 {
+        self.diffs[j] + self.offset
+    }
+
+    fn from_points(points: impl IntoIterator<Item = (i128, i64, i128)>) -> Self {
+        let mut block = Self::default();
+        for (value, diff, width) in points {
+            block.values.push(value);
+            block.diffs.push(diff);
+            block.widths.push(width);
+            block.contribute(block.values.len() - 1, 1);
+        }
+        block
+    }
+
+    fn points(&self) -> impl Iterator<Item = (i128, i64, i128)> + '_ {
+        (0..self.values.len()).map(|j| (self.values[j], self.diff(j), self.widths[j]))
+    }
+}
+
+/// Difference `D(x) = left(≤ x) - right(≤ x)` of the counting functions of
+/// both lists over every value in either list. For lists of equal lengths the
+/// total distance of the sorted pairing is `Σ |D(x)| * (next(x) - x)`.
+/// Points are split into blocks of about `√points`, so an update touches one
+/// block point by point and shifts the following blocks lazily.
+#[derive(Debug, Default)]
+struct CdfDiff {
+    blocks: Vec<Block>,
+    points: usize,
+}
+
+impl CdfDiff {
+    fn locate(&self, value: i128) -> (usize, Result<usize, usize>) {
+        let k = self
+            .blocks
+            .partition_point(|block| block.values[0] <= value)
+            .saturating_sub(1);
+        (k, self.blocks[k].values.binary_search(&value))
+    }
+
+    /// Position of `value`, inserting a point for it if it is new.
+    fn ensure_point(&mut self, value: i128) -> (usize, usize) {
+        if self.blocks.is_empty() {
+            self.blocks.push(Block::from_points([(value, 0, 0)]));
+            self.points = 1;
+            return (0, 0);
+        }
+        let (k, j) = match self.locate(value) {
+            (k, Ok(j)) => return (k, j),
+            (k, Err(j)) => (k, j),
+        };
+        let prev = match j {
+            0 if value < self.blocks[k].values[0] => None,
+            0 => Some((k - 1, self.blocks[k - 1].values.len() - 1)),
+            _ => Some((k, j - 1)),
+        };
+        let next = if j < self.blocks[k].values.len() {
+            Some((k, j))
+        } else {
+            (k + 1 < self.blocks.len()).then_some((k + 1, 0))
+        };
+        let diff = prev.map_or(0, |(pk, pj)| self.blocks[pk].diff(pj));
+        let width = next.map_or(0, |(nk, nj)| self.blocks[nk].values[nj] - value);
+        if let Some((pk, pj)) = prev {
+            let prev_value = self.blocks[pk].values[pj];
+            self.blocks[pk].set_width(pj, value - prev_value);
+        }
+
+        let block = &mut self.blocks[k];
+        block.values.insert(j, value);
+        block.diffs.insert(j, diff - block.offset);
+        block.widths.insert(j, width);
+        block.contribute(j, 1);
+        self.points += 1;
+
+        if block.values.len() > 2 * (self.points.isqrt() + 8) {
+            let mid = block.values.len() / 2;
+            let points: Vec<_> = block.points().collect();
+            let (left, right) = points.split_at(mid);
+            self.blocks[k] = Block::from_points(left.iter().copied());
+            self.blocks
+                .insert(k + 1, Block::from_points(right.iter().copied()));
+            return if j < mid { (k, j) } else { (k + 1, j - mid) };
+        }
+        (k, j)
+    }
+
+    /// Removes the point of `value` once neither list has it, so that `D` is
+    /// the same there as at the previous point, or 0 without one.
+    fn remove_point(&mut self, value: i128) {
+        if self.blocks.is_empty() {
+            return;
+        }
+        let (k, Ok(j)) = self.locate(value) else {
+            return;
+        };
+        let prev = match j {
+            0 if k == 0 => None,
+            0 => Some((k - 1, self.blocks[k - 1].values.len() - 1)),
+            _ => Some((k, j - 1)),
+        };
+        let next = if j + 1 < self.blocks[k].values.len() {
+            Some((k, j + 1))
+        } else {
+            (k + 1 < self.blocks.len()).then_some((k + 1, 0))
+        };
+        if let Some((pk, pj)) = prev {
+            let prev_value = self.blocks[pk].values[pj];
+            let width = next.map_or(0, |(nk, nj)| self.blocks[nk].values[nj] - prev_value);
+            self.blocks[pk].set_width(pj, width);
+        }
+
+        let block = &mut self.blocks[k];
+        block.contribute(j, -1);
+        block.values.remove(j);
+        block.diffs.remove(j);
+        block.widths.remove(j);
+        self.points -= 1;
+
+        let len = block.values.len();
+        if len == 0 {
+            self.blocks.remove(k);
+        } else if k + 1 < self.blocks.len()
+            && len + self.blocks[k + 1].values.len() <= self.points.isqrt() + 8
+        {
+            let next = self.blocks.remove(k + 1);
+            let points: Vec<_> = self.blocks[k].points().chain(next.points()).collect();
+            self.blocks[k] = Block::from_points(points);
+        }
+    }
+
+    /// Adds `delta` to `D(x)` for every `x >= value`.
+    fn add_from(&mut self, value: i128, delta: i64) {
+        let (k, j) = self.ensure_point(value);
+        let block = &mut self.blocks[k];
+        for j in j..block.values.len() {
+            block.add_point(j, delta);
+        }
+        for block in &mut self.blocks[k + 1..] {
+            block.add_all(delta);
+        }
+    }
+
+    fn sum(&self) -> u128 {
+        self.blocks.iter().map(|block| block.abs_sum as u128).sum()
+    }
+}
+
+/// Both location lists with their similarity score and total distance kept
+/// up to date under insertions and removals: the score in O(1) and the
+/// distance in O(√distinct values) per edit, counting the values currently
+/// in either list.
+#[derive(Debug)]
+pub struct SimilarityIndex<T> {
+    occurrences: [HashMap<T, usize>; 2],
+    lens: [usize; 2],
+    similarity_score: i128,
+    cdf_diff: CdfDiff,
+}
+
+impl<T> Default for SimilarityIndex<T> {
+    fn default() -> Self {
+        Self {
+            occurrences: [HashMap::new(), HashMap::new()],
+            lens: [0, 0],
+            similarity_score: 0,
+            cdf_diff: CdfDiff::default(),
+        }
+    }
+}
+
+impl<T: LocationId + Into<i128>> SimilarityIndex<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_lists(a: impl IntoIterator<Item = T>, b: impl IntoIterator<Item = T>) -> Self {
+        let mut index = Self::new();
+        a.into_iter().for_each(|id| {
+            index.insert(List::Left, id);
+        });
+        b.into_iter().for_each(|id| {
+            index.insert(List::Right, id);
+        });
+        index
+    }
+
+    fn update(&mut self, list: List, id: T, delta: i64) {
+        let (this, other) = match list {
+            List::Left => (0, 1),
+            List::Right => (1, 0),
+        };
+        let other_count = self.occurrences[other]
+            .get(&id)
+            .copied()
+            .unwrap_or_default();
+        let value: i128 = id.into();
+        self.similarity_score += delta as i128 * value * other_count as i128;
+        let diff_delta = if list == List::Left { delta } else { -delta };
+        self.cdf_diff.add_from(value, diff_delta);
+        self.lens[this] = self.lens[this].wrapping_add_signed(delta as isize);
+    }
+
+    pub fn insert(&mut self, list: List, id: T) -> Scores {
+        let index = if list == List::Left { 0 } else { 1 };
+        *self.occurrences[index].entry(id).or_default() += 1;
+        self.update(list, id, 1);
+        self.scores()
+    }
+
+    /// Removes one occurrence of `id`, or returns `None` if `list` has none.
+    pub fn remove(&mut self, list: List, id: T) -> Option<Scores> {
+        let index = if list == List::Left { 0 } else { 1 };
+        let count = self.occurrences[index].get_mut(&id)?;
+        *count -= 1;
+        if *count == 0 {
+            self.occurrences[index].remove(&id);
+        }
+        self.update(list, id, -1);
+        if !self.occurrences.iter().any(|o| o.contains_key(&id)) {
+            self.cdf_diff.remove_point(id.into());
+        }
+        Some(self.scores())
+    }
+
+    pub fn similarity_score(&self) -> i128 {
+        self.similarity_score
+    }
+
+    pub fn total_distance(&self) -> Option<u128> {
+        (self.lens[0] == self.lens[1]).then(|| self.cdf_diff.sum())
+    }
+
+    pub fn scores(&self) -> Scores {
+        Scores {
+            similarity_score: self.similarity_score(),
+            total_distance: self.total_distance(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_input, similarity_score, total_distance, DAY};
+    use common::test_input;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_from_lists() {
+        let (a, b) = load_input(test_input(DAY, ""));
+        let index = SimilarityIndex::from_lists(a, b);
+        assert_eq!(
+            index.scores(),
+            Scores {
+                similarity_score: 31,
+                total_distance: Some(11)
+            }
+        );
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut index = SimilarityIndex::new();
+        assert_eq!(index.insert(List::Left, 3).total_distance, None);
+        assert_eq!(
+            index.insert(List::Right, 3),
+            Scores {
+                similarity_score: 3,
+                total_distance: Some(0)
+            }
+        );
+        index.insert(List::Left, -2i64);
+        assert_eq!(
+            index.insert(List::Right, 5),
+            Scores {
+                similarity_score: 3,
+                total_distance: Some(7)
+            }
+        );
+        assert_eq!(index.remove(List::Right, 4), None);
+        assert_eq!(index.remove(List::Right, 3).unwrap().similarity_score, 0);
+        assert_eq!(
+            index.insert(List::Right, -2),
+            Scores {
+                similarity_score: -2,
+                total_distance: Some(2)
+            }
+        );
+    }
+
+    #[test]
+    fn test_removed_values_are_pruned() {
+        let mut index = SimilarityIndex::new();
+        for round in 0..3 {
+            let values = round * 2_000..(round + 1) * 2_000;
+            for id in values.clone() {
+                index.insert(List::Left, id);
+                index.insert(List::Right, id + 1);
+            }
+            assert_eq!(index.total_distance(), Some(2_000));
+            for id in values {
+                index.remove(List::Left, id).unwrap();
+                index.remove(List::Right, id + 1).unwrap();
+            }
+            assert_eq!(index.cdf_diff.points, 0);
+            assert!(index.cdf_diff.blocks.is_empty());
+        }
+        index.insert(List::Left, 5);
+        assert_eq!(index.insert(List::Right, 2).total_distance, Some(3));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_edits(
+            edits in prop::collection::vec(
+                (0usize..2, -200i32..200, any::<bool>(), any::<prop::sample::Index>()),
+                0..300,
+            ),
+        ) {
+            let mut index = SimilarityIndex::new();
+            let mut lists: [Vec<i32>; 2] = [vec![], vec![]];
+            for (list, id, remove, position) in edits {
+                let scores = if remove && !lists[list].is_empty() {
+                    let id = lists[list].swap_remove(position.index(lists[list].len()));
+                    index.remove([List::Left, List::Right][list], id).unwrap()
+                } else {
+                    lists[list].push(id);
+                    index.insert([List::Left, List::Right][list], id)
+                };
+                let [a, b] = lists.clone();
+                let mut distinct: Vec<_> = a.iter().chain(&b).collect();
+                distinct.sort_unstable();
+                distinct.dedup();
+                prop_assert_eq!(index.cdf_diff.points, distinct.len());
+                prop_assert_eq!(
+                    index.cdf_diff.blocks.iter().map(|block| block.values.len()).sum::<usize>(),
+                    distinct.len()
+                );
+                prop_assert_eq!(scores.similarity_score, similarity_score(a.clone(), b.clone()));
+                prop_assert_eq!(scores.total_distance, total_distance(a, b));
+            }
+        }
+    }
+}
@@ -14,23 +14,194 @@
   limitations under the License.
 */
 
+mod dense;
+mod external;
+mod index;
+mod metric;
+mod report;
+
+pub use external::external_total_distance;
+pub use index::{List, Scores, SimilarityIndex};
+pub use metric::{EarthMover, IntersectionSize, Jaccard, Metric, MetricKind, SimilarityScore};
+pub use report::{Contribution, Report};
+
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::hash::Hash;
+use std::io::{self, BufRead, BufReader, ErrorKind};
+use std::iter;
 use std::path::Path;
+use std::str::FromStr;
 
 pub const DAY: &str = "01";
 
-fn parse_line(line: &str) -> Option<(u32, u32)> {
-    let mut it = line.split_whitespace().map_while(|s| s.parse().ok());
-    Some((it.next()?, it.next()?))
+/// Integer type of at most 64 bits usable as a location ID. Distances and
+/// similarity scores are accumulated in 128 bits. A total distance cannot
+/// overflow for any list that fits in memory. A similarity score grows like
+/// `|a| * |b| * 2^64`, so it is exact while `|a| * |b| < 2^63`, e.g. for
+/// lists shorter than 2^31 IDs each.
+pub trait LocationId: Copy + Ord + Hash + FromStr {
+    fn distance(self, other: Self) -> u128;
+
+    /// `self` times `occurrences`, exact for any count below `isize::MAX`.
+    fn similarity(self, occurrences: usize) -> i128;
+
+    /// `self - min` as an index, or `None` if it doesn't fit in `usize`.
+    fn offset_from(self, min: Self) -> Option<usize>;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_location_id {
+    ($($id:ty),* $(,)?) => {
+        $(
+            impl LocationId for $id {
+                fn distance(self, other: Self) -> u128 {
+                    self.abs_diff(other) as u128
+                }
+
+                fn similarity(self, occurrences: usize) -> i128 {
+                    self as i128 * occurrences as i128
+                }
+
+                fn offset_from(self, min: Self) -> Option<usize> {
+                    usize::try_from(self.abs_diff(min)).ok()
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_location_id!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+fn parse_line<T: FromStr>(line: &str) -> Option<Vec<T>> {
+    line.split_whitespace().map(|s| s.parse().ok()).collect()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Whitespace separated columns of IDs, one list per column.
+pub fn parse_columns<T: FromStr>(reader: impl BufRead) -> io::Result<Vec<Vec<T>>> {
+    let mut columns: Vec<Vec<T>> = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let row = parse_line(line.as_str())
+            .filter(|row: &Vec<_>| columns.is_empty() || row.len() == columns.len())
+            .ok_or_else(|| invalid_data(format!("invalid line: {line:?}")))?;
+        if columns.is_empty() {
+            columns.resize_with(row.len(), Vec::new);
+        }
+        iter::zip(&mut columns, row).for_each(|(column, id)| column.push(id));
+    }
+    Ok(columns)
+}
+
+pub fn load_columns<T: FromStr>(path: impl AsRef<Path>) -> Vec<Vec<T>> {
+    parse_columns(BufReader::new(File::open(path).unwrap())).unwrap()
+}
+
+pub fn parse_input(reader: impl BufRead) -> io::Result<(Vec<u32>, Vec<u32>)> {
+    let mut columns = parse_columns(reader)?;
+    match columns.len() {
+        0 => Ok((vec![], vec![])),
+        2 => {
+            let b = columns.pop().unwrap();
+            Ok((columns.pop().unwrap(), b))
+        }
+        n => Err(invalid_data(format!("expected 2 columns, found {n}"))),
+    }
 }
 
 pub fn load_input(path: impl AsRef<Path>) -> (Vec<u32>, Vec<u32>) {
-    let file = BufReader::new(File::open(path).unwrap());
-    file.lines()
-        .map_while(Result::ok)
-        .filter(|line| !line.is_empty())
-        .filter_map(|line| parse_line(line.as_str()))
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
+}
+
+fn sorted_distance<T: LocationId>(a: &[T], b: &[T]) -> Option<u128> {
+    (a.len() == b.len()).then(|| iter::zip(a, b).map(|(a, b)| a.distance(*b)).sum())
+}
+
+/// Sum of the distances between the lists paired in sorted order, or `None`
+/// if their lengths differ.
+pub fn total_distance<T: LocationId>(a: Vec<T>, b: Vec<T>) -> Option<u128> {
+    sorted_distance(&dense::sort_ids(a), &dense::sort_ids(b))
+}
+
+/// [`total_distance`] without the bounded range fast path.
+pub fn comparison_total_distance<T: LocationId>(mut a: Vec<T>, mut b: Vec<T>) -> Option<u128> {
+    a.sort_unstable();
+    b.sort_unstable();
+    sorted_distance(&a, &b)
+}
+
+fn occurrences<T: LocationId>(ids: impl IntoIterator<Item = T>) -> HashMap<T, usize> {
+    ids.into_iter().fold(HashMap::new(), |mut acc, id| {
+        *acc.entry(id).or_default() += 1;
+        acc
+    })
+}
+
+fn similarity_with<T: LocationId>(
+    a: impl IntoIterator<Item = T>,
+    b_occurrences: &HashMap<T, usize>,
+) -> i128 {
+    a.into_iter()
+        .filter_map(|a| b_occurrences.get(&a).map(|&count| a.similarity(count)))
+        .sum()
+}
+
+pub fn similarity_score<T: LocationId>(
+    a: impl IntoIterator<Item = T>,
+    b: impl IntoIterator<Item = T>,
+) -> i128 {
+    let b: Vec<_> = b.into_iter().collect();
+    let mut a = a.into_iter();
+    dense::dense_similarity_score(a.by_ref(), &b)
+        .unwrap_or_else(|| similarity_with(a, &occurrences(b)))
+}
+
+/// [`similarity_score`] without the bounded range fast path.
+pub fn hashed_similarity_score<T: LocationId>(
+    a: impl IntoIterator<Item = T>,
+    b: impl IntoIterator<Item = T>,
+) -> i128 {
+    similarity_with(a, &occurrences(b))
+}
+
+/// Total distance between every pair of columns `(i, j)` with `i < j`, `None`
+/// for columns of different lengths.
+pub fn total_distances<T: LocationId>(columns: Vec<Vec<T>>) -> Vec<((usize, usize), Option<u128>)> {
+    let columns: Vec<_> = columns.into_iter().map(dense::sort_ids).collect();
+    (0..columns.len())
+        .flat_map(|i| (i + 1..columns.len()).map(move |j| (i, j)))
+        .map(|(i, j)| ((i, j), sorted_distance(&columns[i], &columns[j])))
+        .collect()
+}
+
+/// Similarity score of column `i` against column `j` for every `i != j`.
+pub fn similarity_scores<T: LocationId>(columns: &[Vec<T>]) -> Vec<((usize, usize), i128)> {
+    let occurrences: Vec<_> = columns
+        .iter()
+        .map(|column| occurrences(column.iter().copied()))
+        .collect();
+    (0..columns.len())
+        .flat_map(|i| {
+            (0..columns.len())
+                .filter(move |&j| j != i)
+                .map(move |j| (i, j))
+        })
+        .map(|(i, j)| {
+            let score = similarity_with(columns[i].iter().copied(), &occurrences[j]);
+            ((i, j), score)
+        })
         .collect()
 }
 
@@ -41,9 +212,25 @@ mod tests {
 
     #[test]
     fn test_parse_line() {
-        assert_eq!(parse_line(""), None);
-        assert_eq!(parse_line("3   4"), Some((3, 4)));
-        assert_eq!(parse_line("35134   63205"), Some((35134, 63205)));
+        assert_eq!(parse_line::<u32>(""), Some(vec![]));
+        assert_eq!(parse_line("3   4"), Some(vec![3u32, 4]));
+        assert_eq!(parse_line("35134   63205"), Some(vec![35134u32, 63205]));
+        assert_eq!(parse_line("-3   4   5"), Some(vec![-3i8, 4, 5]));
+        assert_eq!(parse_line::<u32>("-3   4"), None);
+        assert_eq!(parse_line::<u32>("3   x"), None);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input(&b""[..]).unwrap(), (vec![], vec![]));
+        assert_eq!(
+            parse_input(&b"3   4\n\n35134   63205\n"[..]).unwrap(),
+            (vec![3, 35134], vec![4, 63205])
+        );
+        assert!(parse_input(&b"3   4\n3\n"[..]).is_err());
+        assert!(parse_input(&b"3   4   5\n"[..]).is_err());
+        assert!(parse_input(&b"3   x\n"[..]).is_err());
+        assert!(parse_input(&b"3   \xff\n"[..]).is_err());
     }
 
     #[test]
@@ -53,4 +240,101 @@ mod tests {
             (vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3])
         );
     }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(
+            parse_columns::<i64>(&b""[..]).unwrap(),
+            Vec::<Vec<i64>>::new()
+        );
+        assert_eq!(
+            parse_columns::<i64>(&b"-3 4 9000000000\n\n1 -2 3\n"[..]).unwrap(),
+            vec![vec![-3, 1], vec![4, -2], vec![9000000000, 3]]
+        );
+        assert!(parse_columns::<i64>(&b"1 2 3\n1 2\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_total_distance() {
+        let (a, b) = load_input(test_input(DAY, ""));
+        assert_eq!(total_distance(a, b), Some(11));
+        assert_eq!(total_distance(vec![1, 2], vec![1]), None);
+        assert_eq!(comparison_total_distance(vec![1], vec![1, 2]), None);
+        assert_eq!(total_distance(Vec::<u8>::new(), vec![]), Some(0));
+        assert_eq!(total_distance(vec![-3i32, 5], vec![4, -1]), Some(3));
+        assert_eq!(
+            total_distance(vec![i64::MIN], vec![i64::MAX]),
+            Some(u64::MAX as u128)
+        );
+        assert_eq!(total_distance(vec![0u8; 300], vec![255; 300]), Some(76_500));
+        assert_eq!(
+            total_distance(vec![i8::MIN; 300], vec![i8::MAX; 300]),
+            Some(76_500)
+        );
+        assert_eq!(
+            total_distance(vec![u64::MIN; 3], vec![u64::MAX; 3]),
+            Some(3 * u64::MAX as u128)
+        );
+
+        let a: Vec<i32> = (0..1000).map(|i| (i * 7919) % 100_003 - 50_000).collect();
+        let b: Vec<i32> = (0..1000).map(|i| (i * 104_729) % 99_991).collect();
+        assert_eq!(
+            total_distance(a.clone(), b.clone()),
+            comparison_total_distance(a, b)
+        );
+    }
+
+    #[test]
+    fn test_similarity_score() {
+        let (a, b) = load_input(test_input(DAY, ""));
+        assert_eq!(similarity_score(a, b), 31);
+        assert_eq!(similarity_score([-3i64, 4], [-3, -3, 4, 5]), -2);
+        assert_eq!(similarity_score([1u64, 2, 2], [2, 1, u64::MAX]), 5);
+        assert_eq!(similarity_score([255u8; 2], vec![255; 300]), 153_000);
+        assert_eq!(similarity_score([i8::MIN; 2], vec![i8::MIN; 300]), -76_800);
+        assert_eq!(
+            hashed_similarity_score([i8::MIN, i8::MAX], vec![i8::MIN, i8::MAX, i8::MAX]),
+            126
+        );
+        assert_eq!(
+            similarity_score([u64::MAX; 2], [u64::MAX; 2]),
+            4 * u64::MAX as i128
+        );
+
+        let a: Vec<u32> = (0..1000).map(|i| 10_000 + (i * 7919) % 997).collect();
+        let b: Vec<u32> = (0..1000).map(|i| 10_000 + (i * 104_729) % 991).collect();
+        assert_eq!(
+            similarity_score(a.clone(), b.clone()),
+            hashed_similarity_score(a, b)
+        );
+    }
+
+    #[test]
+    fn test_total_distances() {
+        let columns = vec![vec![1i32, 2], vec![2, 4], vec![-1, 1]];
+        assert_eq!(
+            total_distances(columns),
+            vec![((0, 1), Some(3)), ((0, 2), Some(3)), ((1, 2), Some(6))]
+        );
+        assert_eq!(
+            total_distances(vec![vec![1u8], vec![2, 3], vec![4]]),
+            vec![((0, 1), None), ((0, 2), Some(3)), ((1, 2), None)]
+        );
+    }
+
+    #[test]
+    fn test_similarity_scores() {
+        let columns = vec![vec![1u64, 2], vec![2, 2], vec![1, 1]];
+        assert_eq!(
+            similarity_scores(&columns),
+            vec![
+                ((0, 1), 4),
+                ((0, 2), 2),
+                ((1, 0), 4),
+                ((1, 2), 0),
+                ((2, 0), 2),
+                ((2, 1), 0)
+            ]
+        );
+    }
 }
@@ -0,0 +1,188 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use crate::{occurrences, similarity_score, LocationId};
+use std::fmt::Display;
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Comparison of two lists of location IDs.
+pub trait Metric<T: LocationId> {
+    type Value: Display;
+
+    fn measure(&self, a: Vec<T>, b: Vec<T>) -> Self::Value;
+}
+
+/// Sum of every ID of `a` times its occurrences in `b` (part 2).
+pub struct SimilarityScore;
+
+/// Number of IDs common to both lists, counted with multiplicity.
+pub struct IntersectionSize;
+
+/// Multiset Jaccard index: intersection size over union size, `1` for two
+/// empty lists.
+pub struct Jaccard;
+
+/// Earth mover's distance between the distributions of both lists, i.e. the
+/// average distance an ID has to move to turn one list into the other. For
+/// lists of equal lengths it is the total distance divided by the length.
+/// `NaN` if exactly one of the lists is empty.
+pub struct EarthMover;
+
+impl<T: LocationId> Metric<T> for SimilarityScore {
+    type Value = i128;
+
+    fn measure(&self, a: Vec<T>, b: Vec<T>) -> i128 {
+        similarity_score(a, b)
+    }
+}
+
+/// Sums of the smaller and the larger count of every ID.
+fn min_max_counts<T: LocationId>(a: Vec<T>, b: Vec<T>) -> (usize, usize) {
+    let (a, mut b) = (occurrences(a), occurrences(b));
+    let (mut min, mut max) = (0, 0);
+    for (id, a_count) in a {
+        let b_count = b.remove(&id).unwrap_or_default();
+        min += a_count.min(b_count);
+        max += a_count.max(b_count);
+    }
+    (min, max + b.values().sum::<usize>())
+}
+
+impl<T: LocationId> Metric<T> for IntersectionSize {
+    type Value = usize;
+
+    fn measure(&self, a: Vec<T>, b: Vec<T>) -> usize {
+        min_max_counts(a, b).0
+    }
+}
+
+impl<T: LocationId> Metric<T> for Jaccard {
+    type Value = f64;
+
+    fn measure(&self, a: Vec<T>, b: Vec<T>) -> f64 {
+        match min_max_counts(a, b) {
+            (_, 0) => 1.0,
+            (intersection, union) => intersection as f64 / union as f64,
+        }
+    }
+}
+
+impl<T: LocationId> Metric<T> for EarthMover {
+    type Value = f64;
+
+    fn measure(&self, mut a: Vec<T>, mut b: Vec<T>) -> f64 {
+        if a.is_empty() || b.is_empty() {
+            return if a.len() == b.len() { 0.0 } else { f64::NAN };
+        }
+        a.sort_unstable();
+        b.sort_unstable();
+        let (a_len, b_len) = (a.len() as f64, b.len() as f64);
+        // Integral of the difference of both cumulative distributions, which
+        // is constant between consecutive distinct IDs.
+        let (mut i, mut j, mut prev, mut sum) = (0, 0, None, 0.0);
+        while let Some(id) = a.get(i).into_iter().chain(b.get(j)).min().copied() {
+            if let Some(prev) = prev {
+                let width = id.to_f64() - T::to_f64(prev);
+                sum += (i as f64 / a_len - j as f64 / b_len).abs() * width;
+            }
+            while a.get(i) == Some(&id) {
+                i += 1;
+            }
+            while b.get(j) == Some(&id) {
+                j += 1;
+            }
+            prev = Some(id);
+        }
+        sum
+    }
+}
+
+/// [`Metric`] selectable by its kebab-case name, e.g. `earth-mover`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Display, EnumIter, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum MetricKind {
+    SimilarityScore,
+    IntersectionSize,
+    Jaccard,
+    EarthMover,
+}
+
+impl MetricKind {
+    pub fn measure<T: LocationId>(self, a: Vec<T>, b: Vec<T>) -> String {
+        match self {
+            MetricKind::SimilarityScore => SimilarityScore.measure(a, b).to_string(),
+            MetricKind::IntersectionSize => IntersectionSize.measure(a, b).to_string(),
+            MetricKind::Jaccard => Jaccard.measure(a, b).to_string(),
+            MetricKind::EarthMover => EarthMover.measure(a, b).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_input, total_distance, DAY};
+    use common::test_input;
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_intersection_and_jaccard() {
+        let (a, b) = load_input(test_input(DAY, ""));
+        assert_eq!(IntersectionSize.measure(a.clone(), b.clone()), 4);
+        assert_eq!(Jaccard.measure(a, b), 0.5);
+        assert_eq!(IntersectionSize.measure(vec![1i8, 1, 2], vec![1, 3]), 1);
+        assert_eq!(Jaccard.measure(vec![1i8, 1, 2], vec![1, 3]), 0.25);
+        assert_eq!(Jaccard.measure(Vec::<u8>::new(), vec![]), 1.0);
+        assert_eq!(Jaccard.measure(vec![], vec![1u8]), 0.0);
+    }
+
+    #[test]
+    fn test_earth_mover() {
+        let (a, b) = load_input(test_input(DAY, ""));
+        let n = a.len() as f64;
+        assert_eq!(
+            EarthMover.measure(a.clone(), b.clone()),
+            total_distance(a, b).unwrap() as f64 / n
+        );
+        assert_eq!(EarthMover.measure(vec![0i32], vec![-1, 1]), 1.0);
+        assert_eq!(EarthMover.measure(vec![0u32, 4], vec![2]), 2.0);
+        assert_eq!(EarthMover.measure(vec![0u32, 1, 2, 3], vec![0, 3]), 0.5);
+        assert_eq!(EarthMover.measure(vec![5u32, 5], vec![5]), 0.0);
+        assert_eq!(EarthMover.measure(Vec::<u32>::new(), vec![]), 0.0);
+        assert!(EarthMover.measure(vec![1u32], vec![]).is_nan());
+    }
+
+    #[test]
+    fn test_metric_kind() {
+        let (a, b) = load_input(test_input(DAY, ""));
+        let measured: Vec<_> = MetricKind::iter()
+            .map(|kind| (kind.to_string(), kind.measure(a.clone(), b.clone())))
+            .collect();
+        assert_eq!(
+            measured,
+            [
+                ("similarity-score", "31"),
+                ("intersection-size", "4"),
+                ("jaccard", "0.5"),
+                ("earth-mover", "1.8333333333333333")
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+        assert_eq!(MetricKind::from_str("jaccard"), Ok(MetricKind::Jaccard));
+        assert!(MetricKind::from_str("cosine").is_err());
+    }
+}
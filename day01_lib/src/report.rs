@@ -0,0 +1,308 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use crate::{occurrences, LocationId};
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+
+const PERCENTILES: [u32; 7] = [0, 25, 50, 75, 90, 99, 100];
+
+/// Contribution of one ID to the similarity score.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Contribution<T = u32> {
+    pub id: T,
+    pub left_count: usize,
+    pub right_count: usize,
+    pub score: i128,
+}
+
+/// Breakdown of both totals of a pair of location lists of equal lengths.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Report<T = u32> {
+    /// Sorted pairing as `(left, right, distance)`.
+    pub pairs: Vec<(T, T, u128)>,
+    pub total_distance: u128,
+    /// Distance counts in power of two buckets `(min, max, count)`.
+    pub histogram: Vec<(u128, u128, usize)>,
+    /// Nearest-rank percentiles of the distances.
+    pub percentiles: Vec<(u32, u128)>,
+    pub only_left: Vec<T>,
+    pub only_right: Vec<T>,
+    pub similarity_score: i128,
+    /// Largest contributions first, at most `top` of them.
+    pub top_contributors: Vec<Contribution<T>>,
+}
+
+fn bucket(distance: u128) -> usize {
+    (u128::BITS - distance.leading_zeros()) as usize
+}
+
+fn bucket_range(bucket: usize) -> (u128, u128) {
+    match bucket {
+        0 => (0, 0),
+        _ => (
+            1 << (bucket - 1),
+            u128::MAX >> (u128::BITS as usize - bucket),
+        ),
+    }
+}
+
+fn percentile(sorted: &[u128], p: u32) -> u128 {
+    let rank = (sorted.len() * p as usize).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+fn only_in<T: Ord + Copy>(ids: &[T], other: &[T]) -> Vec<T> {
+    let mut only: Vec<_> = ids
+        .iter()
+        .copied()
+        .filter(|id| other.binary_search(id).is_err())
+        .collect();
+    only.dedup();
+    only
+}
+
+impl<T: LocationId> Report<T> {
+    /// Report on both lists, or `None` if their lengths differ.
+    pub fn new(mut a: Vec<T>, mut b: Vec<T>, top: usize) -> Option<Self> {
+        if a.len() != b.len() {
+            return None;
+        }
+        a.sort_unstable();
+        b.sort_unstable();
+        let pairs: Vec<_> = iter::zip(&a, &b)
+            .map(|(&a, &b)| (a, b, a.distance(b)))
+            .collect();
+
+        let mut distances: Vec<_> = pairs.iter().map(|&(_, _, distance)| distance).collect();
+        distances.sort_unstable();
+        let mut histogram = vec![];
+        for &distance in &distances {
+            let bucket = bucket(distance);
+            if histogram.len() <= bucket {
+                histogram.resize(bucket + 1, 0);
+            }
+            histogram[bucket] += 1;
+        }
+        let percentiles = if distances.is_empty() {
+            vec![]
+        } else {
+            PERCENTILES
+                .iter()
+                .map(|&p| (p, percentile(&distances, p)))
+                .collect()
+        };
+
+        let b_occurrences = occurrences(b.iter().copied());
+        let mut contributors: Vec<_> = occurrences(a.iter().copied())
+            .into_iter()
+            .filter_map(|(id, left_count)| {
+                let right_count = *b_occurrences.get(&id)?;
+                Some(Contribution {
+                    id,
+                    left_count,
+                    right_count,
+                    score: id.similarity(left_count * right_count),
+                })
+            })
+            .collect();
+        contributors.sort_unstable_by(|c1, c2| c2.score.cmp(&c1.score).then(c1.id.cmp(&c2.id)));
+        let similarity_score = contributors.iter().map(|c| c.score).sum();
+        contributors.truncate(top);
+
+        Some(Self {
+            total_distance: distances.iter().sum(),
+            pairs,
+            histogram: histogram
+                .into_iter()
+                .enumerate()
+                .map(|(bucket, count)| {
+                    let (min, max) = bucket_range(bucket);
+                    (min, max, count)
+                })
+                .collect(),
+            percentiles,
+            only_left: only_in(&a, &b),
+            only_right: only_in(&b, &a),
+            similarity_score,
+            top_contributors: contributors,
+        })
+    }
+
+    pub fn median(&self) -> Option<u128> {
+        self.percentiles
+            .iter()
+            .find(|&&(p, _)| p == 50)
+            .map(|&(_, value)| value)
+    }
+}
+
+fn write_ids<T: Display>(f: &mut Formatter<'_>, label: &str, ids: &[T]) -> fmt::Result {
+    write!(f, "{label} ({}):", ids.len())?;
+    ids.iter().try_for_each(|id| write!(f, " {id}"))?;
+    writeln!(f)
+}
+
+impl<T: Display> Display for Report<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pairs:")?;
+        for (a, b, distance) in &self.pairs {
+            writeln!(f, "{a:>10} {b:>10} {distance:>10}")?;
+        }
+        writeln!(f, "total distance: {}", self.total_distance)?;
+        writeln!(f, "distance histogram:")?;
+        for (min, max, count) in &self.histogram {
+            writeln!(f, "{:>23} {count}", format!("{min}..={max}"))?;
+        }
+        writeln!(f, "distance percentiles:")?;
+        for (p, value) in &self.percentiles {
+            writeln!(f, "{:>5} {value}", format!("p{p}"))?;
+        }
+        write_ids(f, "only in left list", &self.only_left)?;
+        write_ids(f, "only in right list", &self.only_right)?;
+        writeln!(f, "similarity score: {}", self.similarity_score)?;
+        writeln!(f, "top similarity contributors:")?;
+        for c in &self.top_contributors {
+            writeln!(
+                f,
+                "{:>10} x {} x {} = {}",
+                c.id, c.left_count, c.right_count, c.score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_input, similarity_score, total_distance, DAY};
+    use common::test_input;
+
+    #[test]
+    fn test_report() {
+        let (a, b) = load_input(test_input(DAY, ""));
+        let report = Report::new(a, b, 2).unwrap();
+        assert_eq!(
+            report,
+            Report {
+                pairs: vec![
+                    (1, 3, 2),
+                    (2, 3, 1),
+                    (3, 3, 0),
+                    (3, 4, 1),
+                    (3, 5, 2),
+                    (4, 9, 5)
+                ],
+                total_distance: 11,
+                histogram: vec![(0, 0, 1), (1, 1, 2), (2, 3, 2), (4, 7, 1)],
+                percentiles: vec![
+                    (0, 0),
+                    (25, 1),
+                    (50, 1),
+                    (75, 2),
+                    (90, 5),
+                    (99, 5),
+                    (100, 5)
+                ],
+                only_left: vec![1, 2],
+                only_right: vec![5, 9],
+                similarity_score: 31,
+                top_contributors: vec![
+                    Contribution {
+                        id: 3,
+                        left_count: 3,
+                        right_count: 3,
+                        score: 27
+                    },
+                    Contribution {
+                        id: 4,
+                        left_count: 1,
+                        right_count: 1,
+                        score: 4
+                    },
+                ],
+            }
+        );
+        assert_eq!(report.median(), Some(1));
+    }
+
+    #[test]
+    fn test_report_totals() {
+        let a = vec![7, 1, 1, 100, 7, 40];
+        let b = vec![1, 7, 7, 7, 3, 1000];
+        let report = Report::new(a.clone(), b.clone(), usize::MAX).unwrap();
+        assert_eq!(
+            Some(report.total_distance),
+            total_distance(a.clone(), b.clone())
+        );
+        assert_eq!(report.similarity_score, similarity_score(a, b));
+        assert_eq!(
+            report
+                .histogram
+                .iter()
+                .map(|&(_, _, count)| count)
+                .sum::<usize>(),
+            6
+        );
+        assert_eq!(report.histogram.last(), Some(&(512, 1023, 1)));
+        assert_eq!(report.only_left, vec![40, 100]);
+        assert_eq!(report.only_right, vec![3, 1000]);
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let report = Report::<u32>::new(vec![], vec![], 10).unwrap();
+        assert_eq!(report.median(), None);
+        assert!(report.histogram.is_empty() && report.top_contributors.is_empty());
+        assert!(report.to_string().contains("total distance: 0\n"));
+    }
+
+    #[test]
+    fn test_generic_report() {
+        assert_eq!(Report::new(vec![1u32, 2], vec![1], 10), None);
+
+        let a = vec![i8::MIN, i8::MIN, 5];
+        let b = vec![i8::MAX, i8::MIN, i8::MIN];
+        let report = Report::new(a.clone(), b.clone(), 1).unwrap();
+        assert_eq!(
+            report.pairs,
+            vec![
+                (i8::MIN, i8::MIN, 0),
+                (i8::MIN, i8::MIN, 0),
+                (5, i8::MAX, 122)
+            ]
+        );
+        assert_eq!(
+            Some(report.total_distance),
+            total_distance(a.clone(), b.clone())
+        );
+        assert_eq!(report.similarity_score, similarity_score(a, b));
+        assert_eq!(report.similarity_score, -512);
+        assert_eq!(report.histogram.last(), Some(&(64, 127, 1)));
+        assert_eq!(report.only_left, vec![5]);
+        assert_eq!(report.only_right, vec![i8::MAX]);
+        assert_eq!(
+            report.top_contributors,
+            vec![Contribution {
+                id: i8::MIN,
+                left_count: 2,
+                right_count: 2,
+                score: -512
+            }]
+        );
+    }
+}
@@ -14,16 +14,24 @@
   limitations under the License.
 */
 
-use common::input;
-use day02_lib::{is_safe_report, load_input, DAY};
+use common::{cached, input, profile};
+use day02_lib::{is_safe_report, load_input, SafetyPolicy, DAY};
 
 fn count_safe_reports(rows: &[Vec<u32>]) -> usize {
-    rows.iter().filter(|row| is_safe_report(row)).count()
+    rows.iter()
+        .filter(|row| is_safe_report(row, &SafetyPolicy::default()))
+        .count()
 }
 
 fn main() {
-    let rows = load_input(input(DAY, ""));
-    println!("{}", count_safe_reports(&rows));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "1", &path, || {
+            let rows = profile("parse", || load_input(&path));
+            profile("part 1", || count_safe_reports(&rows))
+        })
+    );
 }
 
 #[cfg(test)]
@@ -33,11 +41,11 @@ mod tests {
 
     #[test]
     fn test_is_safe_report() {
-        assert!(is_safe_report(&[7, 6, 4, 2, 1]));
-        assert!(is_safe_report(&[1, 2, 3]));
-        assert!(!is_safe_report(&[1, 2, 1]));
-        assert!(is_safe_report(&[1, 2]));
-        assert!(!is_safe_report(&[1]));
+        assert!(is_safe_report(&[7, 6, 4, 2, 1], &SafetyPolicy::default()));
+        assert!(is_safe_report(&[1, 2, 3], &SafetyPolicy::default()));
+        assert!(!is_safe_report(&[1, 2, 1], &SafetyPolicy::default()));
+        assert!(is_safe_report(&[1, 2], &SafetyPolicy::default()));
+        assert!(!is_safe_report(&[1], &SafetyPolicy::default()));
     }
 
     #[test]
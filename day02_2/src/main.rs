@@ -14,34 +14,116 @@
   limitations under the License.
 */
 
-use common::input;
-use day02_lib::{is_safe_report, load_input, DAY};
+use common::{cached, input, profile};
+use day02_lib::{
+    check_report_with_dampener, is_safe_with_dampener, load_input, parse_line, SafetyPolicy,
+    Verdict, DAY,
+};
+use std::env;
+use std::io::{self, BufRead, BufWriter, ErrorKind, Write};
+use std::process::ExitCode;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+
+const USAGE: &str = "usage: day02_2 [--filter [safe|safe-with-dampener|unsafe]]";
 
 fn count_safe_reports(rows: &[Vec<u32>]) -> usize {
-    let mut buffer: Vec<u32> = vec![];
+    let policy = SafetyPolicy::default();
     rows.iter()
-        .filter(|&row| {
-            debug_assert!(!row.is_empty());
-            if is_safe_report(row) {
-                return true;
-            }
-            buffer.reserve(row.len() - 1);
-            for i in 0..row.len() {
-                buffer.extend(&row[..i]);
-                buffer.extend(&row[i + 1..]);
-                if is_safe_report(&buffer) {
-                    return true;
-                }
-                buffer.clear();
-            }
-            false
-        })
+        .filter(|row| is_safe_with_dampener(row, &policy))
         .count()
 }
 
-fn main() {
-    let rows = load_input(input(DAY, ""));
-    println!("{}", count_safe_reports(&rows));
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+enum Category {
+    Safe,
+    SafeWithDampener,
+    Unsafe,
+}
+
+impl Category {
+    fn of(row: &[u32], policy: &SafetyPolicy) -> Self {
+        match check_report_with_dampener(row, policy) {
+            Verdict::Safe => Category::Safe,
+            Verdict::Dampened(_) => Category::SafeWithDampener,
+            Verdict::Unsafe(_) => Category::Unsafe,
+        }
+    }
+}
+
+/// Copies every report of `reader` to `out` prefixed with its category, or
+/// only the reports of category `only` as they are. Blank lines are skipped,
+/// and invalid ones are reported to `errors` and skipped. Returns the number
+/// of invalid lines.
+fn filter(
+    reader: impl BufRead,
+    mut out: impl Write,
+    mut errors: impl Write,
+    only: Option<Category>,
+) -> io::Result<usize> {
+    let policy = SafetyPolicy::default();
+    let mut invalid = 0;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(row) = parse_line(&line) else {
+            writeln!(errors, "invalid line {}: {line:?}", i + 1)?;
+            invalid += 1;
+            continue;
+        };
+        let category = Category::of(&row, &policy);
+        match only {
+            None => writeln!(out, "{category}\t{line}")?,
+            Some(only) if only == category => writeln!(out, "{line}")?,
+            Some(_) => {}
+        }
+    }
+    out.flush()?;
+    Ok(invalid)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => {
+            let path = input(DAY, "");
+            println!(
+                "{}",
+                cached(DAY, "2", &path, || {
+                    let rows = profile("parse", || load_input(&path));
+                    profile("part 2", || count_safe_reports(&rows))
+                })
+            );
+        }
+        [flag, only @ ..] if flag == "--filter" && only.len() <= 1 => {
+            let only = match only.first().map(|name| Category::from_str(name)) {
+                Some(Ok(category)) => Some(category),
+                Some(Err(_)) => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+                None => None,
+            };
+            let out = BufWriter::new(io::stdout().lock());
+            match filter(io::stdin().lock(), out, io::stderr().lock(), only) {
+                Ok(0) => {}
+                Ok(_) => return ExitCode::FAILURE,
+                Err(err) if err.kind() != ErrorKind::BrokenPipe => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+                Err(_) => {}
+            }
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
 }
 
 #[cfg(test)]
@@ -54,4 +136,48 @@ mod tests {
         let rows = load_input(test_input(DAY, ""));
         assert_eq!(count_safe_reports(&rows), 4);
     }
+
+    fn filtered(only: Option<Category>) -> String {
+        let input = std::fs::read(test_input(DAY, "")).unwrap();
+        let mut out = vec![];
+        assert_eq!(
+            filter(input.as_slice(), &mut out, io::sink(), only).unwrap(),
+            0
+        );
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_filter() {
+        assert_eq!(
+            filtered(None),
+            "safe\t7 6 4 2 1\n\
+             unsafe\t1 2 7 8 9\n\
+             unsafe\t9 7 6 2 1\n\
+             safe-with-dampener\t1 3 2 4 5\n\
+             safe-with-dampener\t8 6 4 4 1\n\
+             safe\t1 3 6 7 9\n"
+        );
+        assert_eq!(
+            filtered(Some(Category::SafeWithDampener)),
+            "1 3 2 4 5\n8 6 4 4 1\n"
+        );
+        assert_eq!(filtered(Some(Category::Unsafe)), "1 2 7 8 9\n9 7 6 2 1\n");
+
+        let (mut out, mut errors) = (vec![], vec![]);
+        let input = b"1 2\n\n1 x\n-1 2\n2 1 1\n";
+        assert_eq!(filter(&input[..], &mut out, &mut errors, None).unwrap(), 2);
+        assert_eq!(out, b"safe\t1 2\nsafe-with-dampener\t2 1 1\n");
+        assert_eq!(
+            errors,
+            b"invalid line 3: \"1 x\"\ninvalid line 4: \"-1 2\"\n"
+        );
+    }
+
+    #[test]
+    fn test_category_names() {
+        assert_eq!(Category::SafeWithDampener.to_string(), "safe-with-dampener");
+        assert_eq!(Category::from_str("unsafe"), Ok(Category::Unsafe));
+        assert!(Category::from_str("dampened").is_err());
+    }
 }
@@ -14,95 +14,405 @@
   limitations under the License.
 */
 
+use std::fmt::{self, Display, Formatter};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, ErrorKind};
+use std::iter;
 use std::path::Path;
 
 pub const DAY: &str = "02";
 
-fn parse_line(line: &str) -> Vec<u32> {
-    line.split_whitespace()
-        .map_while(|s| s.parse().ok())
+pub fn parse_line(line: &str) -> Option<Vec<u32>> {
+    line.split_whitespace().map(|s| s.parse().ok()).collect()
+}
+
+pub fn parse_input(reader: impl BufRead) -> io::Result<Vec<Vec<u32>>> {
+    reader
+        .lines()
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|line| {
+            let line = line?;
+            parse_line(line.as_str()).ok_or_else(|| {
+                io::Error::new(ErrorKind::InvalidData, format!("invalid line: {line:?}"))
+            })
+        })
         .collect()
 }
 
 pub fn load_input(path: impl AsRef<Path>) -> Vec<Vec<u32>> {
-    let file = BufReader::new(File::open(path).unwrap());
-    file.lines()
-        .map_while(Result::ok)
-        .filter(|line| !line.is_empty())
-        .map(|line| parse_line(line.as_str()))
-        .collect()
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
 }
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
-enum Direction<'a> {
-    Increasing(&'a [u32]),
-    Decreasing(&'a [u32]),
+/// Value of a report level.
+pub trait Level: Copy + Ord {
+    /// `self - lower` for `self >= lower`, or `None` if the difference does not
+    /// fit in `Self`, as between far apart levels of a signed type.
+    fn gap(self, lower: Self) -> Option<Self>;
 }
 
-macro_rules! group_adjacent {
-    ($head:expr, $tail:expr, $fn:ident) => {
-        $tail
-            .iter()
-            .scan(*$head, |prev, it| {
-                let result = (*prev, *it);
-                *prev = *it;
-                Some(result)
-            })
-            .all(|(prev, it)| $fn(prev, it))
+macro_rules! impl_level {
+    ($($level:ty),* $(,)?) => {
+        $(
+            impl Level for $level {
+                fn gap(self, lower: Self) -> Option<Self> {
+                    self.checked_sub(lower)
+                }
+            }
+        )*
     };
 }
 
-fn increasing_in_limits(a: u32, b: u32) -> bool {
-    a < b && (1..=3).contains(&(b - a))
+impl_level!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Direction a report may take.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum AllowedDirection {
+    Increasing,
+    Decreasing,
+    Either,
+}
+
+/// Rules for a safe report: every step between adjacent levels is in
+/// `min_step..=max_step` and goes in the same allowed direction, except flat
+/// steps when `allow_flat` is set.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct SafetyPolicy<T = u32> {
+    pub min_step: T,
+    pub max_step: T,
+    pub direction: AllowedDirection,
+    pub allow_flat: bool,
+}
+
+impl<T: From<u8>> Default for SafetyPolicy<T> {
+    fn default() -> Self {
+        Self {
+            min_step: T::from(1),
+            max_step: T::from(3),
+            direction: AllowedDirection::Either,
+            allow_flat: false,
+        }
+    }
+}
+
+/// Why a step between two adjacent levels is not safe.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum StepViolation {
+    Flat,
+    TooSmall,
+    TooLarge,
+    DirectionChange,
+    DisallowedDirection,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Violation<T = u32> {
+    /// Fewer than two levels.
+    TooShort,
+    /// Step from the level at `index` to the next one.
+    Step {
+        index: usize,
+        levels: (T, T),
+        kind: StepViolation,
+    },
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Verdict<T = u32> {
+    Safe,
+    /// Safe once the level at this index is removed.
+    Dampened(usize),
+    /// First violation of the whole report.
+    Unsafe(Violation<T>),
 }
 
-fn decreasing_in_limits(a: u32, b: u32) -> bool {
-    increasing_in_limits(b, a)
+impl<T> Verdict<T> {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe(_))
+    }
 }
 
-impl<'a> Direction<'a> {
-    fn check_direction(&'a self) -> bool {
+impl Display for StepViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StepViolation::Flat => "flat step",
+            StepViolation::TooSmall => "step too small",
+            StepViolation::TooLarge => "step too large",
+            StepViolation::DirectionChange => "direction change",
+            StepViolation::DisallowedDirection => "disallowed direction",
+        })
+    }
+}
+
+impl<T: Display> Display for Violation<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Direction::Increasing([head, tail @ ..]) => {
-                group_adjacent!(head, tail, increasing_in_limits)
+            Violation::TooShort => write!(f, "fewer than two levels"),
+            Violation::Step {
+                index,
+                levels: (a, b),
+                kind,
+            } => write!(f, "{kind} from {a} to {b} at level {index}"),
+        }
+    }
+}
+
+impl<T: Display> Display for Verdict<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::Dampened(index) => write!(f, "safe without level {index}"),
+            Verdict::Unsafe(violation) => write!(f, "unsafe: {violation}"),
+        }
+    }
+}
+
+/// Shape of a kept prefix of a report.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+enum Trend {
+    Single,
+    Flat,
+    Increasing,
+    Decreasing,
+}
+
+const TRENDS: [Trend; 4] = [
+    Trend::Single,
+    Trend::Flat,
+    Trend::Increasing,
+    Trend::Decreasing,
+];
+
+impl<T: Level> SafetyPolicy<T> {
+    /// Trend after extending a prefix of trend `trend` ending in `a` by `b`.
+    fn step(&self, trend: Trend, a: T, b: T) -> Result<Trend, StepViolation> {
+        if a == b {
+            return match (self.allow_flat, trend) {
+                (false, _) => Err(StepViolation::Flat),
+                (true, Trend::Single) => Ok(Trend::Flat),
+                (true, trend) => Ok(trend),
+            };
+        }
+        // A gap too wide for `T` is wider than any `max_step`.
+        let (next, step) = if a < b {
+            (Trend::Increasing, b.gap(a))
+        } else {
+            (Trend::Decreasing, a.gap(b))
+        };
+        let allowed = match self.direction {
+            AllowedDirection::Increasing => next == Trend::Increasing,
+            AllowedDirection::Decreasing => next == Trend::Decreasing,
+            AllowedDirection::Either => true,
+        };
+        if !allowed {
+            Err(StepViolation::DisallowedDirection)
+        } else if matches!(trend, Trend::Increasing | Trend::Decreasing) && trend != next {
+            Err(StepViolation::DirectionChange)
+        } else {
+            match step {
+                Some(step) if step < self.min_step => Err(StepViolation::TooSmall),
+                Some(step) if step <= self.max_step => Ok(next),
+                _ => Err(StepViolation::TooLarge),
             }
-            Direction::Decreasing([head, tail @ ..]) => {
-                group_adjacent!(head, tail, decreasing_in_limits)
+        }
+    }
+}
+
+/// Streaming check of the levels of a report against a [`SafetyPolicy`],
+/// keeping only the last level.
+#[derive(Clone, Debug)]
+pub struct Validator<'a, T = u32> {
+    policy: &'a SafetyPolicy<T>,
+    last: Option<T>,
+    trend: Trend,
+    len: usize,
+    violation: Option<Violation<T>>,
+}
+
+impl<'a, T: Level> Validator<'a, T> {
+    pub fn new(policy: &'a SafetyPolicy<T>) -> Self {
+        Self {
+            policy,
+            last: None,
+            trend: Trend::Single,
+            len: 0,
+            violation: None,
+        }
+    }
+
+    /// Checks the next level. Once a violation is found it is returned for
+    /// every following level.
+    pub fn push(&mut self, level: T) -> Result<(), Violation<T>> {
+        if let Some(violation) = self.violation {
+            return Err(violation);
+        }
+        if let Some(last) = self.last {
+            match self.policy.step(self.trend, last, level) {
+                Ok(trend) => self.trend = trend,
+                Err(kind) => {
+                    let violation = Violation::Step {
+                        index: self.len - 1,
+                        levels: (last, level),
+                        kind,
+                    };
+                    self.violation = Some(violation);
+                    return Err(violation);
+                }
             }
-            _ => true,
         }
+        self.last = Some(level);
+        self.len += 1;
+        Ok(())
     }
 
-    fn try_from(row: &'a [u32]) -> Option<Direction<'a>> {
-        match row {
-            [a, b, ..] if increasing_in_limits(*a, *b) => Some(Direction::Increasing(&row[1..])),
-            [a, b, ..] if decreasing_in_limits(*a, *b) => Some(Direction::Decreasing(&row[1..])),
-            _ => None,
+    /// Verdict on the levels pushed so far.
+    pub fn verdict(&self) -> Verdict<T> {
+        match self.violation {
+            Some(violation) => Verdict::Unsafe(violation),
+            None if self.len < 2 => Verdict::Unsafe(Violation::TooShort),
+            None => Verdict::Safe,
         }
     }
 }
 
-pub fn is_safe_report(row: &[u32]) -> bool {
-    Direction::try_from(row)
-        .filter(|dir| dir.check_direction())
-        .is_some()
+/// Verdict on `levels`, consumed only up to the first violation.
+pub fn validate<T: Level>(
+    levels: impl IntoIterator<Item = T>,
+    policy: &SafetyPolicy<T>,
+) -> Verdict<T> {
+    let mut validator = Validator::new(policy);
+    let _ = levels
+        .into_iter()
+        .try_for_each(|level| validator.push(level));
+    validator.verdict()
+}
+
+pub fn check_report<T: Level>(row: &[T], policy: &SafetyPolicy<T>) -> Verdict<T> {
+    validate(row.iter().copied(), policy)
+}
+
+pub fn is_safe_report<T: Level>(row: &[T], policy: &SafetyPolicy<T>) -> bool {
+    check_report(row, policy).is_safe()
+}
+
+/// Fewest removals of a safe prefix, and its previous kept level and trend.
+type Best = Option<(usize, Option<(usize, Trend)>)>;
+
+/// Indices of the fewest levels, at most `k`, whose removal makes `row`
+/// safe. Keeps, for every level and trend, the fewest removals of a safe
+/// prefix ending in that level, so it runs in O(n * k).
+fn removed_levels<T: Level>(row: &[T], policy: &SafetyPolicy<T>, k: usize) -> Option<Vec<usize>> {
+    let mut best: Vec<[Best; 4]> = Vec::with_capacity(row.len());
+    for (i, &level) in row.iter().enumerate() {
+        let mut cell = [None; 4];
+        cell[Trend::Single as usize] = Some((i, None)).filter(|&(removed, _)| removed <= k);
+        for j in i.saturating_sub(k + 1)..i {
+            for (trend, prev) in iter::zip(TRENDS, best[j]) {
+                let Some(removed) = prev.map(|(removed, _)| removed + i - j - 1) else {
+                    continue;
+                };
+                if let Ok(next) = policy.step(trend, row[j], level) {
+                    let cell = &mut cell[next as usize];
+                    if removed <= k && cell.is_none_or(|(best, _)| removed < best) {
+                        *cell = Some((removed, Some((j, trend))));
+                    }
+                }
+            }
+        }
+        best.push(cell);
+    }
+
+    let (mut i, mut trend, _) = best
+        .iter()
+        .enumerate()
+        .flat_map(|(i, cell)| {
+            iter::zip(&TRENDS[1..], &cell[1..])
+                .filter_map(move |(&trend, best)| best.map(|(removed, _)| (i, trend, removed)))
+        })
+        .map(|(i, trend, removed)| (i, trend, removed + row.len() - 1 - i))
+        .filter(|&(.., removed)| removed <= k)
+        .min_by_key(|&(.., removed)| removed)?;
+    let mut kept = vec![false; row.len()];
+    loop {
+        kept[i] = true;
+        match best[i][trend as usize].unwrap().1 {
+            Some(prev) => (i, trend) = prev,
+            None => break,
+        }
+    }
+    Some((0..row.len()).filter(|&i| !kept[i]).collect())
+}
+
+/// Whether removing at most `k` levels makes `row` safe.
+pub fn is_safe_with_removals<T: Level>(row: &[T], policy: &SafetyPolicy<T>, k: usize) -> bool {
+    removed_levels(row, policy, k).is_some()
+}
+
+/// Whether `row` is safe after removing at most one level, in linear time.
+pub fn is_safe_with_dampener<T: Level>(row: &[T], policy: &SafetyPolicy<T>) -> bool {
+    is_safe_with_removals(row, policy, 1)
+}
+
+/// Safe report left after dropping the fewest levels of another one.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SafeSubsequence<T = u32> {
+    /// Indices of the dropped levels.
+    pub removed: Vec<usize>,
+    pub levels: Vec<T>,
+}
+
+/// Longest safe subsequence of `row`, in O(n^2), or `None` if not even two
+/// of its levels form a safe report.
+pub fn longest_safe_subsequence<T: Level>(
+    row: &[T],
+    policy: &SafetyPolicy<T>,
+) -> Option<SafeSubsequence<T>> {
+    let removed = removed_levels(row, policy, row.len())?;
+    let mut removed_iter = removed.iter().peekable();
+    let levels = row
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| removed_iter.next_if_eq(&&i).is_none())
+        .map(|(_, &level)| level)
+        .collect();
+    Some(SafeSubsequence { removed, levels })
+}
+
+/// [`check_report`] allowing one level to be removed.
+pub fn check_report_with_dampener<T: Level>(row: &[T], policy: &SafetyPolicy<T>) -> Verdict<T> {
+    match check_report(row, policy) {
+        Verdict::Unsafe(violation) => match removed_levels(row, policy, 1).as_deref() {
+            Some(&[index]) => Verdict::Dampened(index),
+            _ => Verdict::Unsafe(violation),
+        },
+        verdict => verdict,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use common::test_input;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parse_line() {
-        assert_eq!(parse_line(""), vec![]);
-        assert_eq!(parse_line("7 6 4 2 1"), vec![7, 6, 4, 2, 1]);
+        assert_eq!(parse_line(""), Some(vec![]));
+        assert_eq!(parse_line("7 6 4 2 1"), Some(vec![7, 6, 4, 2, 1]));
         assert_eq!(
             parse_line("11 12 15 18 19 18"),
-            vec![11, 12, 15, 18, 19, 18]
+            Some(vec![11, 12, 15, 18, 19, 18])
+        );
+        assert_eq!(parse_line("1 2 x 3"), None);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input(&b""[..]).unwrap(), Vec::<Vec<u32>>::new());
+        assert_eq!(
+            parse_input(&b"7 6\n \n1\n"[..]).unwrap(),
+            vec![vec![7, 6], vec![1]]
         );
+        assert!(parse_input(&b"7 -6\n"[..]).is_err());
     }
 
     #[test]
@@ -121,36 +431,268 @@ mod tests {
     }
 
     #[test]
-    fn test_eval_direction() {
+    fn test_validate() {
+        let policy = SafetyPolicy::default();
+        let step = |index, levels, kind| {
+            Verdict::Unsafe(Violation::Step {
+                index,
+                levels,
+                kind,
+            })
+        };
+        assert_eq!(validate([7, 6, 4, 2, 1], &policy), Verdict::Safe);
+        assert_eq!(validate([], &policy), Verdict::Unsafe(Violation::TooShort));
+        assert_eq!(validate([0], &policy), Verdict::Unsafe(Violation::TooShort));
+        assert_eq!(validate([0, 3], &policy), Verdict::Safe);
+        assert_eq!(validate([1, 0], &policy), Verdict::Safe);
+        assert_eq!(
+            validate([0, 7], &policy),
+            step(0, (0, 7), StepViolation::TooLarge)
+        );
+        assert_eq!(
+            validate([0, 0], &policy),
+            step(0, (0, 0), StepViolation::Flat)
+        );
+        assert_eq!(
+            validate([0, 1, 0], &policy),
+            step(1, (1, 0), StepViolation::DirectionChange)
+        );
+        assert_eq!(
+            validate([-3i64, -1, 0], &SafetyPolicy::default()),
+            Verdict::Safe
+        );
+        assert_eq!(
+            validate([5u8, 3, 3], &SafetyPolicy::default()),
+            step(1, (3, 3), StepViolation::Flat)
+        );
+    }
+
+    #[test]
+    fn test_signed_extremes() {
+        let step = |index, levels, kind| {
+            Verdict::Unsafe(Violation::Step {
+                index,
+                levels,
+                kind,
+            })
+        };
+        let policy = SafetyPolicy {
+            min_step: 1i8,
+            max_step: 3,
+            direction: AllowedDirection::Either,
+            allow_flat: false,
+        };
+        assert_eq!(
+            validate([-100, 100], &policy),
+            step(0, (-100, 100), StepViolation::TooLarge)
+        );
+        assert!(is_safe_with_dampener(&[i8::MIN, 0, 2, 4], &policy));
+        let wide = SafetyPolicy {
+            max_step: i8::MAX,
+            ..policy
+        };
+        assert_eq!(validate([i8::MIN, -1, i8::MAX - 1], &wide), Verdict::Safe);
+        assert_eq!(
+            validate([i8::MAX, i8::MIN], &wide),
+            step(0, (i8::MAX, i8::MIN), StepViolation::TooLarge)
+        );
+        assert!(!validate([i64::MIN, i64::MAX], &SafetyPolicy::default()).is_safe());
+        assert_eq!(0u8.gap(255), None);
+        assert_eq!((-1i8).gap(i8::MIN), Some(i8::MAX));
+    }
+
+    fn is_safe_with_removals_reference(row: &[u32], policy: &SafetyPolicy, k: usize) -> bool {
+        (0u32..1 << row.len())
+            .filter(|removed| removed.count_ones() as usize <= k)
+            .any(|removed| {
+                let kept: Vec<_> = (0..row.len())
+                    .filter(|i| removed & 1 << i == 0)
+                    .map(|i| row[i])
+                    .collect();
+                is_safe_report(&kept, policy)
+            })
+    }
+
+    #[test]
+    fn test_is_safe_with_dampener() {
+        let policy = SafetyPolicy::default();
+        let safe: Vec<_> = load_input(test_input(DAY, ""))
+            .iter()
+            .map(|row| is_safe_with_dampener(row, &policy))
+            .collect();
+        assert_eq!(safe, [true, false, false, true, true, true]);
+        assert!(is_safe_with_dampener(&[9, 1, 2, 3], &policy));
+        assert!(is_safe_with_dampener(&[1, 2, 3, 9], &policy));
+        assert!(!is_safe_with_dampener(&[1, 9], &policy));
+        assert!(!is_safe_with_dampener(&[1], &policy));
+    }
+
+    fn policy() -> impl Strategy<Value = SafetyPolicy> {
+        prop_oneof![
+            Just(SafetyPolicy::default()),
+            Just(SafetyPolicy {
+                min_step: 0,
+                max_step: 2,
+                direction: AllowedDirection::Increasing,
+                allow_flat: true,
+            }),
+            Just(SafetyPolicy {
+                direction: AllowedDirection::Decreasing,
+                allow_flat: true,
+                ..SafetyPolicy::default()
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_is_safe_with_removals(
+            row in prop::collection::vec(0u32..8, 0..9),
+            policy in policy(),
+            k in 0usize..4,
+        ) {
+            let signed: Vec<_> = row.iter().map(|&level| level as i64 - 4).collect();
+            let signed_policy = SafetyPolicy {
+                min_step: policy.min_step as i64,
+                max_step: policy.max_step as i64,
+                direction: policy.direction,
+                allow_flat: policy.allow_flat,
+            };
+            prop_assert_eq!(
+                check_report(&row, &policy).is_safe(),
+                check_report(&signed, &signed_policy).is_safe()
+            );
+            prop_assert_eq!(
+                is_safe_with_removals(&row, &policy, k),
+                is_safe_with_removals_reference(&row, &policy, k)
+            );
+        }
+
+        #[test]
+        fn prop_longest_safe_subsequence(row in prop::collection::vec(0u32..10, 0..10)) {
+            let policy = SafetyPolicy::default();
+            let expected =
+                (0..=row.len()).find(|&k| is_safe_with_removals_reference(&row, &policy, k));
+            let subsequence = longest_safe_subsequence(&row, &policy);
+            prop_assert_eq!(subsequence.as_ref().map(|s| s.removed.len()), expected);
+            if let Some(SafeSubsequence { removed, levels }) = subsequence {
+                prop_assert!(is_safe_report(&levels, &policy));
+                prop_assert_eq!(removed.len() + levels.len(), row.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_report() {
+        let policy = SafetyPolicy::default();
+        let step = |index, levels, kind| {
+            Verdict::Unsafe(Violation::Step {
+                index,
+                levels,
+                kind,
+            })
+        };
+        let verdicts: Vec<_> = load_input(test_input(DAY, ""))
+            .iter()
+            .map(|row| check_report(row, &policy))
+            .collect();
+        assert_eq!(
+            verdicts,
+            [
+                Verdict::Safe,
+                step(1, (2, 7), StepViolation::TooLarge),
+                step(2, (6, 2), StepViolation::TooLarge),
+                step(1, (3, 2), StepViolation::DirectionChange),
+                step(2, (4, 4), StepViolation::Flat),
+                Verdict::Safe,
+            ]
+        );
+        assert_eq!(
+            check_report(&[1], &policy),
+            Verdict::Unsafe(Violation::TooShort)
+        );
+        let policy = SafetyPolicy {
+            min_step: 2,
+            direction: AllowedDirection::Increasing,
+            ..policy
+        };
+        assert_eq!(
+            check_report(&[1, 3, 4], &policy),
+            step(1, (3, 4), StepViolation::TooSmall)
+        );
+        assert_eq!(
+            check_report(&[3, 1], &policy),
+            step(0, (3, 1), StepViolation::DisallowedDirection)
+        );
+    }
+
+    #[test]
+    fn test_check_report_with_dampener() {
+        let policy = SafetyPolicy::default();
+        let rows = load_input(test_input(DAY, ""));
+        let verdicts: Vec<_> = rows
+            .iter()
+            .map(|row| check_report_with_dampener(row, &policy))
+            .collect();
+        assert_eq!(verdicts[0], Verdict::Safe);
+        assert_eq!(verdicts[1], check_report(&rows[1], &policy));
+        assert_eq!(verdicts[2], check_report(&rows[2], &policy));
+        for (row, verdict) in iter::zip(&rows, &verdicts).skip(3).take(2) {
+            let &Verdict::Dampened(index) = verdict else {
+                panic!("{row:?} {verdict:?}");
+            };
+            let mut row = row.clone();
+            row.remove(index);
+            assert!(is_safe_report(&row, &policy));
+        }
+        assert_eq!(
+            check_report_with_dampener(
+                &[3, 4, 4, 3, 2],
+                &SafetyPolicy {
+                    allow_flat: true,
+                    ..policy
+                }
+            ),
+            Verdict::Dampened(0)
+        );
+        assert_eq!(verdicts[5], Verdict::Safe);
+    }
+
+    #[test]
+    fn test_verdict_display() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(check_report(&[1, 2, 3], &policy).to_string(), "safe");
+        assert_eq!(
+            check_report(&[1, 3, 2, 4, 5], &policy).to_string(),
+            "unsafe: direction change from 3 to 2 at level 1"
+        );
         assert_eq!(
-            Direction::try_from(&[7, 6, 4, 2, 1]),
-            Some(Direction::Decreasing(&[6, 4, 2, 1]))
+            check_report_with_dampener(&[1, 2, 3, 9], &policy).to_string(),
+            "safe without level 3"
         );
-        assert_eq!(Direction::try_from(&[]), None);
-        assert_eq!(Direction::try_from(&[0]), None);
-        assert_eq!(Direction::try_from(&[0, 7]), None);
-        assert_eq!(Direction::try_from(&[0, 0]), None);
         assert_eq!(
-            Direction::try_from(&[0, 3]),
-            Some(Direction::Increasing(&[3]))
+            check_report(&[], &policy).to_string(),
+            "unsafe: fewer than two levels"
         );
     }
 
     #[test]
-    fn test_check_direction() {
-        assert!(Direction::Increasing(&[]).check_direction());
-        assert!(Direction::Decreasing(&[]).check_direction());
-        assert!(Direction::Increasing(&[0]).check_direction());
-        assert!(Direction::Decreasing(&[1]).check_direction());
-        assert!(Direction::Decreasing(&[1, 0]).check_direction());
-        assert!(!Direction::Increasing(&[1, 0]).check_direction());
-        assert!(!Direction::Decreasing(&[0, 1]).check_direction());
-        assert!(Direction::Increasing(&[0, 1]).check_direction());
-        assert!(!Direction::Decreasing(&[0, 4]).check_direction());
-        assert!(!Direction::Increasing(&[0, 4]).check_direction());
-        assert!(!Direction::Decreasing(&[0, 0]).check_direction());
-        assert!(!Direction::Increasing(&[0, 0]).check_direction());
-        assert!(!Direction::Decreasing(&[0, 1, 0]).check_direction());
-        assert!(!Direction::Increasing(&[0, 1, 0]).check_direction());
+    fn test_longest_safe_subsequence() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(
+            longest_safe_subsequence(&[1, 9, 2, 3, 9, 9, 4, 0], &policy),
+            Some(SafeSubsequence {
+                removed: vec![1, 4, 5, 7],
+                levels: vec![1, 2, 3, 4]
+            })
+        );
+        assert_eq!(
+            longest_safe_subsequence(&[1, 2, 3], &policy),
+            Some(SafeSubsequence {
+                removed: vec![],
+                levels: vec![1, 2, 3]
+            })
+        );
+        assert_eq!(longest_safe_subsequence(&[1, 9, 20], &policy), None);
     }
 }
@@ -14,63 +14,61 @@
   limitations under the License.
 */
 
-use common::input;
-use day03_lib::{load_input, DAY};
-use regex::Regex;
-use std::sync::LazyLock;
+use common::{cached, input, profile};
+use day03_lib::{highlight, load_input, load_instructions, Disassembly, Interpreter, DAY};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind, Read};
+use std::process::ExitCode;
 
-#[derive(Debug, Eq, PartialEq)]
-enum Instruction {
-    Mul(u32, u32),
-    Do,
-    Dont,
-}
-
-impl Instruction {
-    fn eval(&self, enabled: bool) -> (Option<u32>, bool) {
-        match self {
-            Instruction::Mul(n1, n2) => (if enabled { Some(n1 * n2) } else { None }, enabled),
-            Instruction::Do => (None, true),
-            Instruction::Dont => (None, false),
-        }
-    }
-}
+const USAGE: &str = "usage: day03_2 [--highlight [PATH] | --disassemble [PATH]]";
 
-fn calc_sum(instructions: &[Instruction]) -> u32 {
-    instructions
-        .iter()
-        .scan(true, |old_state, instr| {
-            let (val, new_state) = instr.eval(*old_state);
-            *old_state = new_state;
-            Some(val)
-        })
-        .flatten()
-        .sum()
+fn calc_sum(reader: impl Read) -> io::Result<i64> {
+    Ok(Interpreter::part_two().run_reader(reader)?.total)
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
-    static RE: LazyLock<Regex> = LazyLock::new(|| {
-        Regex::new(r"(do\(\))|(don't\(\))|mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap()
-    });
-    RE.captures_iter(input)
-        .map(|c| {
-            if c.get(1).is_some() {
-                Instruction::Do
-            } else if c.get(2).is_some() {
-                Instruction::Dont
-            } else if let Some((n1, n2)) = c.get(3).and_then(|m1| c.get(4).map(|m2| (m1, m2))) {
-                Instruction::Mul(n1.as_str().parse().unwrap(), n2.as_str().parse().unwrap())
-            } else {
-                unreachable!()
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [] => {
+            let path = input(DAY, "");
+            println!(
+                "{}",
+                cached(DAY, "2", &path, || {
+                    let file = File::open(&path).unwrap();
+                    profile("part 2", || calc_sum(file).unwrap())
+                })
+            );
+        }
+        [flag, path @ ..] if flag == "--highlight" && path.len() <= 1 => {
+            let path = path.first().map_or_else(|| input(DAY, ""), Into::into);
+            let input = load_input(path);
+            let out = BufWriter::new(io::stdout().lock());
+            match highlight(&Interpreter::part_two(), &input, out) {
+                Err(err) if err.kind() != ErrorKind::BrokenPipe => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+                _ => {}
             }
-        })
-        .collect()
-}
-
-fn main() {
-    let input = load_input(input(DAY, ""));
-    let input = String::from_utf8_lossy(input.as_ref());
-    println!("{}", calc_sum(parse_input(input.as_ref()).as_slice()));
+        }
+        [flag, path @ ..] if flag == "--disassemble" && path.len() <= 1 => {
+            let path = path.first().map_or_else(|| input(DAY, ""), Into::into);
+            let instructions = load_instructions(path);
+            match Disassembly::new(&Interpreter::part_two(), &instructions) {
+                Ok(disassembly) => print!("{disassembly}"),
+                Err(err) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
 }
 
 #[cfg(test)]
@@ -78,27 +76,9 @@ mod tests {
     use super::*;
     use common::test_input;
 
-    #[test]
-    fn test_parse_input() {
-        let input = load_input(test_input(DAY, "2"));
-        let input = String::from_utf8_lossy(input.as_slice());
-        assert_eq!(
-            parse_input(input.as_ref()),
-            vec![
-                Instruction::Mul(2, 4),
-                Instruction::Dont,
-                Instruction::Mul(5, 5),
-                Instruction::Mul(11, 8),
-                Instruction::Do,
-                Instruction::Mul(8, 5),
-            ]
-        );
-    }
-
     #[test]
     fn test_calc_sum() {
-        let input = load_input(test_input(DAY, "2"));
-        let input = String::from_utf8_lossy(input.as_slice());
-        assert_eq!(calc_sum(parse_input(input.as_ref()).as_slice()), 48)
+        let file = File::open(test_input(DAY, "2")).unwrap();
+        assert_eq!(calc_sum(file).unwrap(), 48)
     }
 }
@@ -0,0 +1,165 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use crate::interpreter::{EvalError, Interpreter, Machine};
+use crate::scanner::Instruction;
+use std::fmt::{self, Display, Formatter};
+
+/// Instruction of a listing with the machine state around it.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Line {
+    pub offset: usize,
+    pub name: &'static str,
+    pub args: Vec<i64>,
+    pub value: i64,
+    /// Whether the machine was enabled when the instruction ran.
+    pub enabled: bool,
+    /// Total after the instruction.
+    pub total: i64,
+}
+
+/// Listing of the instructions an interpreter runs, with statistics.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Disassembly {
+    pub lines: Vec<Line>,
+    /// Instructions run of every registered kind, in registration order.
+    pub counts: Vec<(&'static str, usize)>,
+    /// Instructions ignored because the machine was disabled.
+    pub disabled: usize,
+    /// Sum of the values of the ignored instructions.
+    pub disabled_sum: i64,
+    pub total: i64,
+}
+
+impl Disassembly {
+    /// Runs the instructions, leaving out the ones the interpreter does not
+    /// know.
+    pub fn new<'a>(
+        interpreter: &Interpreter,
+        instructions: impl IntoIterator<Item = &'a Instruction>,
+    ) -> Result<Self, EvalError> {
+        let mut counts: Vec<_> = interpreter
+            .definitions()
+            .iter()
+            .map(|d| (d.name, 0))
+            .collect();
+        let mut disassembly = Self {
+            lines: vec![],
+            counts: vec![],
+            disabled: 0,
+            disabled_sum: 0,
+            total: 0,
+        };
+        let mut machine = Machine::default();
+        for instruction in instructions {
+            let before = machine;
+            let Some(value) = interpreter.execute(&mut machine, instruction)? else {
+                continue;
+            };
+            if before.ignored(&machine) {
+                disassembly.disabled += 1;
+                disassembly.disabled_sum =
+                    disassembly
+                        .disabled_sum
+                        .checked_add(value)
+                        .ok_or_else(|| EvalError {
+                            instruction: instruction.clone(),
+                        })?;
+            }
+            if let Some((_, count)) = counts.iter_mut().find(|(n, _)| *n == instruction.name) {
+                *count += 1;
+            }
+            disassembly.lines.push(Line {
+                offset: instruction.span.start,
+                name: instruction.name,
+                args: instruction.args.clone(),
+                value,
+                enabled: before.enabled,
+                total: machine.total,
+            });
+        }
+        disassembly.counts = counts;
+        disassembly.total = machine.total;
+        Ok(disassembly)
+    }
+}
+
+impl Display for Disassembly {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>8}  {:<20} {:<7} {:>12} {:>12}",
+            "offset", "instruction", "enabled", "value", "total"
+        )?;
+        for line in &self.lines {
+            let args: Vec<_> = line.args.iter().map(i64::to_string).collect();
+            writeln!(
+                f,
+                "{:>8}  {:<20} {:<7} {:>12} {:>12}",
+                line.offset,
+                format!("{}({})", line.name, args.join(",")),
+                if line.enabled { "yes" } else { "no" },
+                line.value,
+                line.total
+            )?;
+        }
+        writeln!(f, "instructions:")?;
+        for (name, count) in &self.counts {
+            writeln!(f, "{name:>8} {count}")?;
+        }
+        writeln!(
+            f,
+            "disabled: {} instructions, sum {}",
+            self.disabled, self.disabled_sum
+        )?;
+        writeln!(f, "total: {}", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_instructions, DAY};
+    use common::test_input;
+
+    #[test]
+    fn test_disassembly() {
+        let instructions = load_instructions(test_input(DAY, "2"));
+        let disassembly = Disassembly::new(&Interpreter::part_two(), &instructions).unwrap();
+        assert_eq!(
+            disassembly.to_string(),
+            "  offset  instruction          enabled        value        total\n\
+            \x20      1  mul(2,4)             yes                8            8\n\
+            \x20     20  don't()              yes                0            8\n\
+            \x20     28  mul(5,5)             no                25            8\n\
+            \x20     48  mul(11,8)            no                88            8\n\
+            \x20     59  do()                 no                 0            8\n\
+            \x20     64  mul(8,5)             yes               40           48\n\
+             instructions:\n\
+            \x20    mul 4\n\
+            \x20     do 1\n\
+            \x20  don't 1\n\
+             disabled: 2 instructions, sum 113\n\
+             total: 48\n"
+        );
+
+        let part_one = Disassembly::new(&Interpreter::part_one(), &instructions).unwrap();
+        assert_eq!(part_one.counts, [("mul", 4)]);
+        assert_eq!((part_one.disabled, part_one.disabled_sum), (0, 0));
+        assert_eq!(part_one.total, 161);
+        assert_eq!(part_one.total, disassembly.total + disassembly.disabled_sum);
+    }
+}
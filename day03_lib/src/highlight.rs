@@ -0,0 +1,113 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use crate::interpreter::{Interpreter, Machine};
+use crate::scanner::Event;
+use std::io::{self, Write};
+
+const RESET: &str = "\x1b[0m";
+/// Instructions that had no effect because the machine was disabled.
+const DISABLED: &str = "\x1b[2m";
+const NEAR_MISS: &str = "\x1b[4;31m";
+/// Colours of the registered instructions, in the order of registration.
+const PALETTE: [&str; 5] = [
+    "\x1b[1;32m",
+    "\x1b[1;36m",
+    "\x1b[1;33m",
+    "\x1b[1;35m",
+    "\x1b[1;34m",
+];
+
+/// Copies `input` to `out` with ANSI colours: every instruction the
+/// interpreter recognizes in the colour of its definition, dimmed if it was
+/// ignored while disabled, and near-misses underlined in red. Fails with
+/// `InvalidData` at the first instruction that cannot be evaluated.
+pub fn highlight(interpreter: &Interpreter, input: &[u8], mut out: impl Write) -> io::Result<()> {
+    let mut events = vec![];
+    let mut scanner = interpreter.scanner();
+    scanner.feed_events(input, |event| events.push(event));
+    scanner.finish(|event| events.push(event));
+
+    let mut machine = Machine::default();
+    let mut written = 0;
+    for event in events {
+        let (span, style) = match event {
+            Event::Instruction(instruction) => {
+                let before = machine;
+                interpreter.execute(&mut machine, &instruction)?;
+                let style = if before.ignored(&machine) {
+                    DISABLED
+                } else {
+                    let definitions = interpreter.definitions();
+                    let index = definitions
+                        .iter()
+                        .position(|d| d.name == instruction.name)
+                        .unwrap_or_default();
+                    PALETTE[index % PALETTE.len()]
+                };
+                (instruction.span, style)
+            }
+            Event::NearMiss(span) => (span, NEAR_MISS),
+        };
+        if span.start < written {
+            continue;
+        }
+        out.write_all(&input[written..span.start])?;
+        out.write_all(style.as_bytes())?;
+        out.write_all(&input[span.clone()])?;
+        out.write_all(RESET.as_bytes())?;
+        written = span.end;
+    }
+    out.write_all(&input[written..])?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_input, DAY};
+    use common::test_input;
+
+    #[test]
+    fn test_highlight() {
+        let input = load_input(test_input(DAY, "2"));
+        let mut out = vec![];
+        highlight(&Interpreter::part_two(), &input, &mut out).unwrap();
+        let [mul, enable, disable, ..] = PALETTE;
+        let styled = |style: &str, text: &str| format!("{style}{text}{RESET}");
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "x{}&{}!^{}_{}+{}({}un{}?{})\n",
+                styled(mul, "mul(2,4)"),
+                styled(NEAR_MISS, "mul[3,7]"),
+                styled(disable, "don't()"),
+                styled(DISABLED, "mul(5,5)"),
+                styled(NEAR_MISS, "mul(32,64]"),
+                styled(DISABLED, "mul(11,8)"),
+                styled(enable, "do()"),
+                styled(mul, "mul(8,5)"),
+            )
+        );
+
+        let mut out = vec![];
+        highlight(&Interpreter::part_one(), b"don't()mul(2,3)", &mut out).unwrap();
+        assert_eq!(
+            out,
+            format!("don't(){}", styled(mul, "mul(2,3)")).as_bytes()
+        );
+    }
+}
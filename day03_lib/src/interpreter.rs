@@ -0,0 +1,435 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use crate::scanner::{Grammar, GrammarError, Instruction, Scanner, Signature};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, ErrorKind, Read};
+
+/// State the instructions act on.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Machine {
+    pub enabled: bool,
+    pub total: i64,
+}
+
+impl Machine {
+    /// Whether an instruction that took the machine from `self` to `after`
+    /// was ignored because the machine was disabled.
+    pub(crate) fn ignored(&self, after: &Machine) -> bool {
+        !self.enabled && self == after
+    }
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            total: 0,
+        }
+    }
+}
+
+/// Instruction known to the interpreter: its value for the operands and
+/// how that value changes the machine. Both return `None` on overflow, and
+/// `eval` also for operands it cannot take, as `sub` registered with arity 1.
+#[derive(Debug, Copy, Clone)]
+pub struct Definition {
+    pub name: &'static str,
+    pub arity: usize,
+    pub eval: fn(&[i64]) -> Option<i64>,
+    pub effect: fn(&mut Machine, i64) -> Option<()>,
+}
+
+fn product(args: &[i64]) -> Option<i64> {
+    args.iter()
+        .try_fold(1i64, |product, &arg| product.checked_mul(arg))
+}
+
+fn sum(args: &[i64]) -> Option<i64> {
+    args.iter().try_fold(0i64, |sum, &arg| sum.checked_add(arg))
+}
+
+fn difference(args: &[i64]) -> Option<i64> {
+    args.first()?.checked_sub(*args.get(1)?)
+}
+
+fn nothing(_: &[i64]) -> Option<i64> {
+    Some(0)
+}
+
+/// Adds the value to the total while the machine is enabled.
+pub fn accumulate(machine: &mut Machine, value: i64) -> Option<()> {
+    if machine.enabled {
+        machine.total = machine.total.checked_add(value)?;
+    }
+    Some(())
+}
+
+pub fn enable(machine: &mut Machine, _: i64) -> Option<()> {
+    machine.enabled = true;
+    Some(())
+}
+
+pub fn disable(machine: &mut Machine, _: i64) -> Option<()> {
+    machine.enabled = false;
+    Some(())
+}
+
+pub fn reset(machine: &mut Machine, _: i64) -> Option<()> {
+    machine.total = 0;
+    Some(())
+}
+
+/// Instruction whose value or effect on the total does not fit in an `i64`,
+/// or whose operands its definition cannot take. The machine is left as it
+/// was before the instruction.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct EvalError {
+    pub instruction: Instruction,
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Instruction { name, args, span } = &self.instruction;
+        let args: Vec<_> = args.iter().map(i64::to_string).collect();
+        write!(
+            f,
+            "cannot evaluate {name}({}) at offset {}",
+            args.join(","),
+            span.start
+        )
+    }
+}
+
+impl Error for EvalError {}
+
+impl From<EvalError> for io::Error {
+    fn from(err: EvalError) -> Self {
+        io::Error::new(ErrorKind::InvalidData, err)
+    }
+}
+
+impl Definition {
+    pub const MUL: Self = Self {
+        name: "mul",
+        arity: 2,
+        eval: product,
+        effect: accumulate,
+    };
+    pub const DO: Self = Self {
+        name: "do",
+        arity: 0,
+        eval: nothing,
+        effect: enable,
+    };
+    pub const DONT: Self = Self {
+        name: "don't",
+        arity: 0,
+        eval: nothing,
+        effect: disable,
+    };
+    pub const ADD: Self = Self {
+        name: "add",
+        arity: 2,
+        eval: sum,
+        effect: accumulate,
+    };
+    pub const SUB: Self = Self {
+        name: "sub",
+        arity: 2,
+        eval: difference,
+        effect: accumulate,
+    };
+    pub const RESET: Self = Self {
+        name: "reset",
+        arity: 0,
+        eval: nothing,
+        effect: reset,
+    };
+
+    pub fn signature(&self) -> Signature {
+        Signature {
+            name: self.name,
+            arity: self.arity,
+        }
+    }
+}
+
+/// Runs instructions against a table of definitions. Instructions missing
+/// from the table are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Interpreter {
+    definitions: Vec<Definition>,
+    grammar: Grammar,
+}
+
+impl Interpreter {
+    pub fn new(definitions: Vec<Definition>) -> Self {
+        let mut interpreter = Self::default();
+        definitions.into_iter().for_each(|definition| {
+            interpreter.register(definition);
+        });
+        interpreter
+    }
+
+    /// Only `mul` counts.
+    pub fn part_one() -> Self {
+        Self::new(vec![Definition::MUL])
+    }
+
+    /// `mul` counts unless disabled by `don't` and not yet re-enabled by `do`.
+    pub fn part_two() -> Self {
+        Self::new(vec![Definition::MUL, Definition::DO, Definition::DONT])
+    }
+
+    /// Adds the definition, replacing any with the same name.
+    pub fn register(&mut self, definition: Definition) -> &mut Self {
+        match self
+            .definitions
+            .iter_mut()
+            .find(|d| d.name == definition.name)
+        {
+            Some(existing) => *existing = definition,
+            None => self.definitions.push(definition),
+        }
+        self
+    }
+
+    /// Sets the operand syntax of all instructions, unless the grammar is
+    /// invalid.
+    pub fn set_grammar(&mut self, grammar: Grammar) -> Result<&mut Self, GrammarError> {
+        grammar.validate()?;
+        self.grammar = grammar;
+        Ok(self)
+    }
+
+    pub fn definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+
+    pub fn definition(&self, name: &str) -> Option<&Definition> {
+        self.definitions.iter().find(|d| d.name == name)
+    }
+
+    /// Scanner for the registered instructions.
+    pub fn scanner(&self) -> Scanner {
+        let signatures = self.definitions.iter().map(Definition::signature).collect();
+        Scanner::with_grammar(signatures, self.grammar).expect("grammar is validated when set")
+    }
+
+    /// Applies the instruction, returning its value if it is registered.
+    pub fn execute(
+        &self,
+        machine: &mut Machine,
+        instruction: &Instruction,
+    ) -> Result<Option<i64>, EvalError> {
+        let Some(definition) = self
+            .definition(instruction.name)
+            .filter(|d| d.arity == instruction.args.len())
+        else {
+            return Ok(None);
+        };
+        let err = || EvalError {
+            instruction: instruction.clone(),
+        };
+        let value = (definition.eval)(&instruction.args).ok_or_else(err)?;
+        let mut after = *machine;
+        (definition.effect)(&mut after, value).ok_or_else(err)?;
+        *machine = after;
+        Ok(Some(value))
+    }
+
+    pub fn run<'a>(
+        &self,
+        instructions: impl IntoIterator<Item = &'a Instruction>,
+    ) -> Result<Machine, EvalError> {
+        let mut machine = Machine::default();
+        for instruction in instructions {
+            self.execute(&mut machine, instruction)?;
+        }
+        Ok(machine)
+    }
+
+    /// Scans `reader` for the registered instructions and runs them as they
+    /// are found. Instructions after an error are scanned but not run.
+    pub fn run_reader(&self, reader: impl Read) -> io::Result<Machine> {
+        let mut machine = Machine::default();
+        let mut error = None;
+        self.scanner().scan(reader, |instruction| {
+            if error.is_none() {
+                error = self.execute(&mut machine, &instruction).err();
+            }
+        })?;
+        match error {
+            Some(err) => Err(err.into()),
+            None => Ok(machine),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::MAX_DIGITS;
+    use crate::{load_input, load_instructions, DAY};
+    use common::test_input;
+
+    #[test]
+    fn test_puzzle() {
+        let instructions = load_instructions(test_input(DAY, "1"));
+        assert_eq!(
+            Interpreter::part_one().run(&instructions).unwrap().total,
+            161
+        );
+        let instructions = load_instructions(test_input(DAY, "2"));
+        assert_eq!(
+            Interpreter::part_one().run(&instructions).unwrap().total,
+            161
+        );
+        assert_eq!(
+            Interpreter::part_two().run(&instructions),
+            Ok(Machine {
+                enabled: true,
+                total: 48
+            })
+        );
+
+        let input = load_input(test_input(DAY, "2"));
+        assert_eq!(
+            Interpreter::part_two()
+                .run_reader(input.as_slice())
+                .unwrap()
+                .total,
+            48
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        let mut interpreter = Interpreter::part_two();
+        interpreter
+            .register(Definition::ADD)
+            .register(Definition::SUB)
+            .register(Definition::RESET);
+        let input = b"add(1,2)mul(2,3)reset()sub(1,5)don't()add(3,3)do()mul(2,4)";
+        assert_eq!(interpreter.run_reader(&input[..]).unwrap().total, 4);
+        assert_eq!(
+            Interpreter::part_two()
+                .run_reader(&input[..])
+                .unwrap()
+                .total,
+            14
+        );
+
+        let invalid = Grammar {
+            min_digits: 0,
+            ..Grammar::default()
+        };
+        assert!(interpreter.set_grammar(invalid).is_err());
+        interpreter
+            .set_grammar(Grammar {
+                signed: true,
+                ..Grammar::default()
+            })
+            .unwrap();
+        assert_eq!(
+            interpreter
+                .run_reader(&b"mul(-2,3)sub(1,-4)add(-1,-1)"[..])
+                .unwrap()
+                .total,
+            -3
+        );
+
+        interpreter.register(Definition {
+            name: "mul",
+            arity: 3,
+            eval: product,
+            effect: accumulate,
+        });
+        assert_eq!(
+            interpreter
+                .run_reader(&b"mul(2,3)mul(2,3,4)"[..])
+                .unwrap()
+                .total,
+            24
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = "9".repeat(MAX_DIGITS as usize);
+        let largest: i64 = max.parse().unwrap();
+        let mut interpreter = Interpreter::part_two();
+        interpreter
+            .register(Definition::ADD)
+            .set_grammar(Grammar {
+                max_digits: MAX_DIGITS,
+                signed: true,
+                ..Grammar::default()
+            })
+            .unwrap();
+
+        let input = format!("add({max},{max})add(-{max},-{max})");
+        let machine = interpreter.run_reader(input.as_bytes()).unwrap();
+        assert_eq!(machine.total, 0);
+
+        let input = format!("add(1,2)mul({max},{max})add(1,2)");
+        let err = interpreter.run_reader(input.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            format!("cannot evaluate mul({largest},{largest}) at offset 8")
+        );
+
+        let input = format!("add({max},{max})").repeat(5);
+        let instructions = interpreter.scanner().parse(input.as_bytes()).unwrap();
+        assert_eq!(
+            interpreter.run(&instructions[..4]).unwrap().total,
+            8 * largest
+        );
+        let err = interpreter.run(&instructions).unwrap_err();
+        assert_eq!(err.instruction, instructions[4]);
+
+        let mut machine = Machine {
+            enabled: true,
+            total: i64::MAX,
+        };
+        assert!(interpreter.execute(&mut machine, &instructions[0]).is_err());
+        assert_eq!(machine.total, i64::MAX);
+        machine.enabled = false;
+        assert_eq!(
+            interpreter.execute(&mut machine, &instructions[0]),
+            Ok(Some(2 * largest))
+        );
+    }
+
+    #[test]
+    fn test_difference_operands() {
+        assert_eq!(difference(&[1, 5]), Some(-4));
+        assert_eq!(difference(&[i64::MIN, 1]), None);
+        assert_eq!(difference(&[1]), None);
+        assert_eq!(difference(&[]), None);
+
+        let mut interpreter = Interpreter::part_one();
+        interpreter.register(Definition {
+            arity: 1,
+            ..Definition::SUB
+        });
+        let err = interpreter.run_reader(&b"mul(2,3)sub(4)"[..]).unwrap_err();
+        assert_eq!(err.to_string(), "cannot evaluate sub(4) at offset 8");
+    }
+}
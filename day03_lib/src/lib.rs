@@ -14,18 +14,39 @@
   limitations under the License.
 */
 
+mod disassembly;
+mod highlight;
+mod interpreter;
+mod scanner;
+
+pub use disassembly::{Disassembly, Line};
+pub use highlight::highlight;
+pub use interpreter::{
+    accumulate, disable, enable, reset, Definition, EvalError, Interpreter, Machine,
+};
+pub use scanner::{
+    parse_instructions, scan, Event, Grammar, GrammarError, Instruction, Scanner, Signature,
+    PUZZLE_SIGNATURES,
+};
+
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{self, BufReader, Read};
 use std::path::Path;
 
 pub const DAY: &str = "03";
 
-pub fn load_input(path: impl AsRef<Path>) -> Vec<u8> {
+pub fn parse_input(mut reader: impl Read) -> io::Result<Vec<u8>> {
     let mut result = vec![];
-    BufReader::new(File::open(path).unwrap())
-        .read_to_end(&mut result)
-        .unwrap();
-    result
+    reader.read_to_end(&mut result)?;
+    Ok(result)
+}
+
+pub fn load_input(path: impl AsRef<Path>) -> Vec<u8> {
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
+}
+
+pub fn load_instructions(path: impl AsRef<Path>) -> Vec<Instruction> {
+    parse_instructions(File::open(path).unwrap()).unwrap()
 }
 
 #[cfg(test)]
@@ -0,0 +1,715 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, ErrorKind, Read};
+use std::mem;
+use std::ops::Range;
+
+const CHUNK_LEN: usize = 64 * 1024;
+/// Most digits of an operand that always fit in `i64`.
+pub(crate) const MAX_DIGITS: u8 = 18;
+/// Longest run of whitespace allowed in an operand list, so that the bytes
+/// kept for a partial match stay bounded.
+const MAX_WHITESPACE: usize = 64;
+
+/// Syntax of the operand list following an instruction name.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Grammar {
+    pub min_digits: u8,
+    pub max_digits: u8,
+    /// Operands may start with `+` or `-`.
+    pub signed: bool,
+    /// ASCII whitespace is allowed around the operands, between the
+    /// delimiters, in runs of at most 64 bytes.
+    pub whitespace: bool,
+    pub open: u8,
+    pub separator: u8,
+    pub close: u8,
+}
+
+impl Default for Grammar {
+    /// Grammar of the puzzle: `(X,Y)` with unsigned operands of one to three
+    /// digits.
+    fn default() -> Self {
+        Self {
+            min_digits: 1,
+            max_digits: 3,
+            signed: false,
+            whitespace: false,
+            open: b'(',
+            separator: b',',
+            close: b')',
+        }
+    }
+}
+
+impl Grammar {
+    /// Checks that `0 < min_digits <= max_digits <= 18`, so that every
+    /// operand fits in an `i64`.
+    pub fn validate(&self) -> Result<(), GrammarError> {
+        if 0 < self.min_digits
+            && self.min_digits <= self.max_digits
+            && self.max_digits <= MAX_DIGITS
+        {
+            Ok(())
+        } else {
+            Err(GrammarError {
+                min_digits: self.min_digits,
+                max_digits: self.max_digits,
+            })
+        }
+    }
+}
+
+/// Digit limits a [`Grammar`] cannot have.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct GrammarError {
+    pub min_digits: u8,
+    pub max_digits: u8,
+}
+
+impl Display for GrammarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid digit limits {}..={}",
+            self.min_digits, self.max_digits
+        )
+    }
+}
+
+impl Error for GrammarError {}
+
+/// Name and number of operands of an instruction the scanner recognizes.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Signature {
+    pub name: &'static str,
+    pub arity: usize,
+}
+
+/// Instructions of the puzzle: `mul(X,Y)`, `do()` and `don't()`.
+pub const PUZZLE_SIGNATURES: [Signature; 3] = [
+    Signature {
+        name: "mul",
+        arity: 2,
+    },
+    Signature {
+        name: "do",
+        arity: 0,
+    },
+    Signature {
+        name: "don't",
+        arity: 0,
+    },
+];
+
+/// Instruction recognized in corrupted memory, with the byte offsets it
+/// spans in the input.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub args: Vec<i64>,
+    pub span: Range<usize>,
+}
+
+/// What the scanner found: an instruction, or the span of a near-miss, a
+/// registered name followed by a bracket that did not form an instruction,
+/// like `mul[3,7]` or `mul(32,64]`.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Event {
+    Instruction(Instruction),
+    NearMiss(Range<usize>),
+}
+
+/// Position inside the operand list.
+#[derive(Debug, Copy, Clone)]
+enum Operand {
+    /// Expecting the next operand.
+    Before,
+    /// After the sign of an operand.
+    Sign,
+    /// Inside an operand, after this many digits.
+    Digits(u8),
+    /// After an operand and whitespace.
+    After,
+}
+
+/// Part of an instruction matched so far.
+#[derive(Debug, Clone)]
+enum State {
+    /// Bytes of the name are in the scanner buffer.
+    Name,
+    /// The name, the opening delimiter and the operands read so far.
+    Args {
+        signature: Signature,
+        args: Vec<i64>,
+        operand: Operand,
+        negative: bool,
+    },
+}
+
+enum Step {
+    Continue,
+    Done(Signature, Vec<i64>),
+    Fail,
+}
+
+/// Incremental scanner for calls `name(X,Y,...)` of the given signatures,
+/// with operands following a [`Grammar`], in arbitrary bytes. Input can be fed
+/// in chunks of any size, and instructions split between chunks are still
+/// found. Only the bytes of the current partial match are kept.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    signatures: Vec<Signature>,
+    grammar: Grammar,
+    state: State,
+    /// Bytes of the current partial match.
+    partial: Vec<u8>,
+    /// Offset of the first byte of `partial`.
+    start: usize,
+    /// Offset of the next byte fed.
+    offset: usize,
+    /// Near-miss with a wrong opening bracket, still growing over the
+    /// operands after it, and the bracket that closes it.
+    near_miss: Option<(Range<usize>, u8)>,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self::new(PUZZLE_SIGNATURES.to_vec())
+    }
+}
+
+impl Scanner {
+    pub fn new(signatures: Vec<Signature>) -> Self {
+        Self::with_grammar(signatures, Grammar::default()).unwrap()
+    }
+
+    pub fn with_grammar(
+        signatures: Vec<Signature>,
+        grammar: Grammar,
+    ) -> Result<Self, GrammarError> {
+        grammar.validate()?;
+        Ok(Self {
+            signatures,
+            grammar,
+            state: State::Name,
+            partial: vec![],
+            start: 0,
+            offset: 0,
+            near_miss: None,
+        })
+    }
+
+    fn step(&mut self, byte: u8) -> Step {
+        let grammar = &self.grammar;
+        let (signature, args, operand, negative) = match &mut self.state {
+            State::Name => {
+                let matched = self.partial.as_slice();
+                let signature = self
+                    .signatures
+                    .iter()
+                    .find(|s| byte == grammar.open && s.name.as_bytes() == matched);
+                return if let Some(&signature) = signature {
+                    self.state = State::Args {
+                        signature,
+                        args: vec![],
+                        operand: Operand::Before,
+                        negative: false,
+                    };
+                    Step::Continue
+                } else if self.signatures.iter().any(|s| {
+                    let name = s.name.as_bytes();
+                    name.starts_with(matched) && name.get(matched.len()) == Some(&byte)
+                }) {
+                    Step::Continue
+                } else {
+                    Step::Fail
+                };
+            }
+            State::Args {
+                signature,
+                args,
+                operand,
+                negative,
+            } => (*signature, args, operand, negative),
+        };
+        let complete = match *operand {
+            Operand::Digits(digits) => digits >= grammar.min_digits,
+            Operand::After => true,
+            Operand::Before | Operand::Sign => false,
+        };
+        let space = grammar.whitespace
+            && byte.is_ascii_whitespace()
+            && self
+                .partial
+                .iter()
+                .rev()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count()
+                < MAX_WHITESPACE;
+        match (*operand, byte) {
+            (Operand::Before, b'+' | b'-') if grammar.signed && args.len() < signature.arity => {
+                *negative = byte == b'-';
+                *operand = Operand::Sign;
+            }
+            (Operand::Before | Operand::Sign | Operand::Digits(_), b'0'..=b'9') => {
+                let digits = match *operand {
+                    Operand::Digits(digits) if digits < grammar.max_digits => digits,
+                    Operand::Before | Operand::Sign if args.len() < signature.arity => {
+                        if let Operand::Before = operand {
+                            *negative = false;
+                        }
+                        args.push(0);
+                        0
+                    }
+                    _ => return Step::Fail,
+                };
+                let d = (byte - b'0') as i64;
+                let last = args.last_mut().unwrap();
+                *last = *last * 10 + if *negative { -d } else { d };
+                *operand = Operand::Digits(digits + 1);
+            }
+            (Operand::Before | Operand::After, _) if space => {}
+            (Operand::Digits(_), _) if space && complete => *operand = Operand::After,
+            _ if byte == grammar.separator && complete && args.len() < signature.arity => {
+                *operand = Operand::Before
+            }
+            _ if byte == grammar.close
+                && args.len() == signature.arity
+                && (complete || matches!(operand, Operand::Before) && args.is_empty()) =>
+            {
+                return Step::Done(signature, mem::take(args));
+            }
+            _ => return Step::Fail,
+        }
+        Step::Continue
+    }
+
+    fn reset(&mut self) {
+        self.state = State::Name;
+        self.partial.clear();
+    }
+
+    fn is_near_miss(&self, byte: u8) -> bool {
+        match self.state {
+            State::Args { .. } => true,
+            State::Name => {
+                byte != self.grammar.open
+                    && b"([{<".contains(&byte)
+                    && self
+                        .signatures
+                        .iter()
+                        .any(|s| s.name.as_bytes() == self.partial)
+            }
+        }
+    }
+
+    fn is_operand_byte(&self, byte: u8) -> bool {
+        let grammar = &self.grammar;
+        byte.is_ascii_digit()
+            || byte == grammar.separator
+            || grammar.signed && (byte == b'+' || byte == b'-')
+            || grammar.whitespace && byte.is_ascii_whitespace()
+    }
+
+    /// Extends the open near-miss over the byte at `at` if it is an operand
+    /// byte or the closing bracket, and emits the near-miss once it ends.
+    fn extend_near_miss(&mut self, byte: u8, at: usize, emit: &mut impl FnMut(Event)) {
+        let operand = self.is_operand_byte(byte);
+        let Some((span, close)) = &mut self.near_miss else {
+            return;
+        };
+        if byte == *close {
+            span.end = at + 1;
+        } else if operand {
+            span.end = at + 1;
+            return;
+        }
+        if let Some((span, _)) = self.near_miss.take() {
+            emit(Event::NearMiss(span));
+        }
+    }
+
+    fn push(&mut self, byte: u8, at: usize, emit: &mut impl FnMut(Event)) {
+        if self.partial.is_empty() {
+            self.start = at;
+        }
+        match self.step(byte) {
+            Step::Continue => self.partial.push(byte),
+            Step::Done(signature, args) => {
+                emit(Event::Instruction(Instruction {
+                    name: signature.name,
+                    args,
+                    span: self.start..at + 1,
+                }));
+                self.reset();
+            }
+            Step::Fail if self.partial.is_empty() => {}
+            Step::Fail => {
+                if self.is_near_miss(byte) {
+                    let end = if b"()[]{}<>".contains(&byte) {
+                        at + 1
+                    } else {
+                        at
+                    };
+                    let open = b"([{<".iter().position(|&b| b == byte);
+                    match open {
+                        Some(i) if matches!(self.state, State::Name) => {
+                            self.near_miss.get_or_insert((self.start..end, b")]}>"[i]));
+                        }
+                        _ => emit(Event::NearMiss(self.start..end)),
+                    }
+                }
+                // Another instruction may start inside the failed match.
+                let mut replay = mem::take(&mut self.partial);
+                replay.push(byte);
+                let start = self.start;
+                self.reset();
+                for (i, &byte) in replay.iter().enumerate().skip(1) {
+                    self.push(byte, start + i, emit);
+                }
+            }
+        }
+    }
+
+    /// Feeds the next chunk, calling `emit` for every instruction it completes.
+    pub fn feed(&mut self, chunk: &[u8], mut emit: impl FnMut(Instruction)) {
+        self.feed_events(chunk, |event| {
+            if let Event::Instruction(instruction) = event {
+                emit(instruction)
+            }
+        })
+    }
+
+    /// Like [`Scanner::feed`], also reporting near-misses. A near-miss with a
+    /// wrong opening bracket is reported once its operands end, so call
+    /// [`Scanner::finish`] after the last chunk.
+    pub fn feed_events(&mut self, chunk: &[u8], mut emit: impl FnMut(Event)) {
+        for &byte in chunk {
+            self.extend_near_miss(byte, self.offset, &mut emit);
+            self.push(byte, self.offset, &mut emit);
+            self.offset += 1;
+        }
+    }
+
+    /// Reports a near-miss still open at the end of the input, like `mul[3,7`.
+    pub fn finish(&mut self, mut emit: impl FnMut(Event)) {
+        if let Some((span, _)) = self.near_miss.take() {
+            emit(Event::NearMiss(span));
+        }
+    }
+
+    /// Scans everything `reader` yields in constant memory.
+    pub fn scan(
+        &mut self,
+        mut reader: impl Read,
+        mut emit: impl FnMut(Instruction),
+    ) -> io::Result<()> {
+        let mut chunk = vec![0; CHUNK_LEN];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) => return Ok(()),
+                Ok(len) => self.feed(&chunk[..len], &mut emit),
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    pub fn parse(&mut self, reader: impl Read) -> io::Result<Vec<Instruction>> {
+        let mut instructions = vec![];
+        self.scan(reader, |instruction| instructions.push(instruction))?;
+        Ok(instructions)
+    }
+}
+
+/// Scans `reader` for the puzzle instructions.
+pub fn scan(reader: impl Read, emit: impl FnMut(Instruction)) -> io::Result<()> {
+    Scanner::default().scan(reader, emit)
+}
+
+pub fn parse_instructions(reader: impl Read) -> io::Result<Vec<Instruction>> {
+    Scanner::default().parse(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_input, DAY};
+    use common::test_input;
+
+    fn calls(instructions: Vec<Instruction>) -> Vec<(&'static str, Vec<i64>)> {
+        instructions.into_iter().map(|i| (i.name, i.args)).collect()
+    }
+
+    fn parsed(input: &[u8]) -> Vec<(&'static str, Vec<i64>)> {
+        calls(parse_instructions(input).unwrap())
+    }
+
+    #[test]
+    fn test_parse_instructions() {
+        let input = load_input(test_input(DAY, "2"));
+        let instruction = |name, args: &[i64], span| Instruction {
+            name,
+            args: args.to_vec(),
+            span,
+        };
+        assert_eq!(
+            parse_instructions(input.as_slice()).unwrap(),
+            vec![
+                instruction("mul", &[2, 4], 1..9),
+                instruction("don't", &[], 20..27),
+                instruction("mul", &[5, 5], 28..36),
+                instruction("mul", &[11, 8], 48..57),
+                instruction("do", &[], 59..63),
+                instruction("mul", &[8, 5], 64..72),
+            ]
+        );
+        assert_eq!(
+            parsed(b"mmul(2,3)mul(1234,5)mul(12,345)"),
+            [("mul", vec![2, 3]), ("mul", vec![12, 345])]
+        );
+        assert_eq!(
+            parsed(b"do(don't()dodo()"),
+            [("don't", vec![]), ("do", vec![])]
+        );
+        assert_eq!(
+            parsed(b"mul(1,)mul(,1)mul (1,1)mul(1,2mul(1,2,3)mul(1)"),
+            []
+        );
+        assert_eq!(
+            parsed(b"\xffmul(\xff1,1)\xffmul(0,7)\xff"),
+            [("mul", vec![0, 7])]
+        );
+        assert_eq!(
+            parse_instructions(&b"mmul(2,3)dodo()"[..]).unwrap(),
+            [
+                instruction("mul", &[2, 3], 1..9),
+                instruction("do", &[], 11..15)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_near_misses() {
+        let near_misses = |input: &[u8]| {
+            let mut events = vec![];
+            let mut scanner = Scanner::default();
+            scanner.feed_events(input, |event| events.push(event));
+            scanner.finish(|event| events.push(event));
+            events
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::NearMiss(span) => {
+                        Some(String::from_utf8_lossy(&input[span]).into_owned())
+                    }
+                    Event::Instruction(_) => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let input = load_input(test_input(DAY, "2"));
+        assert_eq!(near_misses(&input), ["mul[3,7]", "mul(32,64]"]);
+        assert_eq!(
+            near_misses(b"mul<1,2>mul{3x}mul[mul(1,2)don't[,]mul[4,5"),
+            ["mul<1,2>", "mul{3", "mul[", "don't[,]", "mul[4,5"]
+        );
+
+        let mut events = vec![];
+        Scanner::default().feed_events(b"do(don't()mul(1,mul(2,3)do_", |e| events.push(e));
+        let spans: Vec<_> = events
+            .into_iter()
+            .map(|event| match event {
+                Event::Instruction(instruction) => (true, instruction.span),
+                Event::NearMiss(span) => (false, span),
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                (false, 0..3),
+                (true, 3..10),
+                (false, 10..16),
+                (true, 16..24)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_signatures() {
+        let mut scanner = Scanner::new(vec![
+            Signature {
+                name: "add",
+                arity: 3,
+            },
+            Signature {
+                name: "do",
+                arity: 0,
+            },
+            Signature {
+                name: "dd",
+                arity: 1,
+            },
+        ]);
+        let instructions = scanner
+            .parse(&b"addo()add(1,2,3)adddd(4)mul(1,2)"[..])
+            .unwrap();
+        assert_eq!(
+            instructions
+                .iter()
+                .map(|i| i.span.clone())
+                .collect::<Vec<_>>(),
+            [2..6, 6..16, 19..24]
+        );
+        assert_eq!(
+            calls(instructions),
+            [("do", vec![]), ("add", vec![1, 2, 3]), ("dd", vec![4])]
+        );
+    }
+
+    #[test]
+    fn test_grammar() {
+        let parsed = |grammar, input: &[u8]| {
+            let mut scanner = Scanner::with_grammar(PUZZLE_SIGNATURES.to_vec(), grammar).unwrap();
+            calls(scanner.parse(input).unwrap())
+        };
+        let signed = Grammar {
+            signed: true,
+            whitespace: true,
+            ..Grammar::default()
+        };
+        assert_eq!(
+            parsed(signed, b"mul( -2 , +3 )mul(1,\t2)mul(- 1,2)mul(1 2,3)do( )"),
+            [("mul", vec![-2, 3]), ("mul", vec![1, 2]), ("do", vec![])]
+        );
+        assert_eq!(parsed(Grammar::default(), b"mul(-2,3)mul( 1,2)"), []);
+
+        let brackets = Grammar {
+            open: b'[',
+            separator: b';',
+            close: b']',
+            ..Grammar::default()
+        };
+        assert_eq!(
+            parsed(brackets, b"mul[3;7]mul(3,7)mul[3,7]don't[]"),
+            [("mul", vec![3, 7]), ("don't", vec![])]
+        );
+
+        let digits = Grammar {
+            min_digits: 2,
+            max_digits: 4,
+            ..Grammar::default()
+        };
+        assert_eq!(
+            parsed(digits, b"mul(1,22)mul(1234,56)mul(12345,67)"),
+            [("mul", vec![1234, 56])]
+        );
+
+        let wide = Grammar {
+            max_digits: 18,
+            signed: true,
+            ..Grammar::default()
+        };
+        assert_eq!(
+            parsed(wide, b"mul(-999999999999999999,999999999999999999)"),
+            [(
+                "mul",
+                vec![-999_999_999_999_999_999, 999_999_999_999_999_999]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_whitespace_runs() {
+        let mut scanner = Scanner::with_grammar(
+            PUZZLE_SIGNATURES.to_vec(),
+            Grammar {
+                whitespace: true,
+                ..Grammar::default()
+            },
+        )
+        .unwrap();
+        let spaces = |len| " ".repeat(len);
+        let mut instructions = vec![];
+        scanner.feed(b"mul(", |i| instructions.push(i));
+        for _ in 0..1000 {
+            scanner.feed(spaces(1000).as_bytes(), |i| instructions.push(i));
+            assert!(scanner.partial.len() <= "mul(".len() + MAX_WHITESPACE);
+        }
+        let input = format!(
+            "1,2)mul({}1,{}2)mul({}3,4)",
+            spaces(MAX_WHITESPACE),
+            spaces(MAX_WHITESPACE),
+            spaces(MAX_WHITESPACE + 1)
+        );
+        scanner.feed(input.as_bytes(), |i| instructions.push(i));
+        assert_eq!(calls(instructions), [("mul", vec![1, 2])]);
+    }
+
+    #[test]
+    fn test_invalid_grammar() {
+        for (min_digits, max_digits) in [(1, 19), (0, 3), (4, 3)] {
+            let grammar = Grammar {
+                min_digits,
+                max_digits,
+                ..Grammar::default()
+            };
+            let err = Scanner::with_grammar(vec![], grammar).unwrap_err();
+            assert_eq!(
+                err,
+                GrammarError {
+                    min_digits,
+                    max_digits
+                }
+            );
+            assert_eq!(
+                err.to_string(),
+                format!("invalid digit limits {min_digits}..={max_digits}")
+            );
+        }
+        assert!(Scanner::with_grammar(
+            vec![],
+            Grammar {
+                min_digits: 18,
+                max_digits: 18,
+                ..Grammar::default()
+            }
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input = load_input(test_input(DAY, "2"));
+        let expected = parse_instructions(input.as_slice()).unwrap();
+        for split in 0..=input.len() {
+            let mut scanner = Scanner::default();
+            let mut instructions = vec![];
+            scanner.feed(&input[..split], |i| instructions.push(i));
+            scanner.feed(&input[split..], |i| instructions.push(i));
+            assert_eq!(instructions, expected, "split at {split}");
+        }
+
+        let mut scanner = Scanner::default();
+        let mut instructions = vec![];
+        input
+            .iter()
+            .for_each(|byte| scanner.feed(&[*byte], |i| instructions.push(i)));
+        assert_eq!(instructions, expected);
+    }
+}
@@ -14,28 +14,43 @@
   limitations under the License.
 */
 
+mod search;
+mod stencil;
+
+pub use search::{find_word, find_words, Direction, Match};
+pub use stencil::{find_stencil, find_stencils, Placement, Stencil};
+
 use std::cell::OnceCell;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, ErrorKind};
 use std::path::Path;
 
 pub const DAY: &str = "04";
 
-pub fn load_input(path: impl AsRef<Path>) -> (Vec<Vec<char>>, usize) {
+pub fn parse_input(mut reader: impl BufRead) -> io::Result<(Vec<Vec<char>>, usize)> {
     let mut result = vec![];
     let mut buffer = String::new();
-    let mut reader = BufReader::new(File::open(path).unwrap());
     let line_size = OnceCell::new();
-    while reader.read_line(&mut buffer).unwrap() > 0 {
+    while reader.read_line(&mut buffer)? > 0 {
         let trimmed = buffer.trim_end();
         if trimmed.is_empty() {
             break;
         }
-        assert_eq!(line_size.get_or_init(|| trimmed.len()), &trimmed.len());
-        result.push(trimmed.chars().collect());
+        let line: Vec<_> = trimmed.chars().collect();
+        if line_size.get_or_init(|| line.len()) != &line.len() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("line {} has different length", result.len()),
+            ));
+        }
+        result.push(line);
         buffer.clear();
     }
-    (result, line_size.get().copied().unwrap_or_default())
+    Ok((result, line_size.get().copied().unwrap_or_default()))
+}
+
+pub fn load_input(path: impl AsRef<Path>) -> (Vec<Vec<char>>, usize) {
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
 }
 
 #[cfg(test)]
@@ -43,6 +58,16 @@ mod tests {
     use super::*;
     use common::test_input;
 
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input(&b""[..]).unwrap(), (vec![], 0));
+        assert_eq!(
+            parse_input(&b"XM\nAS\n\nXMAS\n"[..]).unwrap(),
+            (vec![vec!['X', 'M'], vec!['A', 'S']], 2)
+        );
+        assert!(parse_input(&b"XM\nASX\n"[..]).is_err());
+    }
+
     #[test]
     fn test_load_input() {
         let input = load_input(test_input(DAY, ""));
@@ -0,0 +1,182 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(EnumIter, Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Direction {
+    Left,
+    Right,
+    UpLeft,
+    Up,
+    UpRight,
+    DownLeft,
+    Down,
+    DownRight,
+}
+
+impl Direction {
+    /// Row and column change of one step.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::Down => (1, 0),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    /// Cell `steps` steps away from `(i, j)` if it is inside a grid of
+    /// `height` rows and `width` columns.
+    pub fn walk(
+        self,
+        (i, j): (usize, usize),
+        steps: usize,
+        (height, width): (usize, usize),
+    ) -> Option<(usize, usize)> {
+        let (di, dj) = self.delta();
+        let i = i.checked_add_signed(di.checked_mul(steps.try_into().ok()?)?)?;
+        let j = j.checked_add_signed(dj.checked_mul(steps.try_into().ok()?)?)?;
+        (i < height && j < width).then_some((i, j))
+    }
+}
+
+/// Word read from `start` in `direction`, `word` being its index in the
+/// searched list.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Match {
+    pub word: usize,
+    pub start: (usize, usize),
+    pub direction: Direction,
+}
+
+/// Finds every placement of the words in the grid, in all eight directions,
+/// in row-major order of the start cell. A word reading the same in several
+/// directions, like a palindrome or a single letter, matches once per
+/// direction. Empty words never match.
+pub fn find_words((grid, line_size): (&[Vec<char>], usize), words: &[&str]) -> Vec<Match> {
+    let words: Vec<Vec<char>> = words.iter().map(|word| word.chars().collect()).collect();
+    let size = (grid.len(), line_size);
+    let mut matches = vec![];
+    for (i, line) in grid.iter().enumerate() {
+        debug_assert_eq!(line.len(), line_size);
+        for (j, &letter) in line.iter().enumerate() {
+            for (index, word) in words.iter().enumerate() {
+                if word.first() != Some(&letter) {
+                    continue;
+                }
+                for direction in Direction::iter() {
+                    let found = word.iter().enumerate().skip(1).all(|(steps, letter)| {
+                        direction
+                            .walk((i, j), steps, size)
+                            .is_some_and(|(i, j)| grid[i][j] == *letter)
+                    });
+                    if found {
+                        matches.push(Match {
+                            word: index,
+                            start: (i, j),
+                            direction,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    matches
+}
+
+pub fn find_word(grid: (&[Vec<char>], usize), word: &str) -> Vec<Match> {
+    find_words(grid, &[word])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load_input, DAY};
+    use common::test_input;
+
+    #[test]
+    fn test_walk() {
+        for dir in Direction::iter() {
+            for i in 0..10 {
+                for j in 0..10 {
+                    assert_eq!(dir.walk((i, j), 0, (10, 10)), Some((i, j)));
+                }
+            }
+        }
+        assert_eq!(Direction::DownLeft.walk((2, 3), 3, (10, 10)), Some((5, 0)));
+        assert_eq!(Direction::DownLeft.walk((2, 3), 4, (10, 10)), None);
+        assert_eq!(Direction::Up.walk((2, 3), 3, (10, 10)), None);
+        assert_eq!(Direction::Right.walk((2, 3), 6, (10, 10)), Some((2, 9)));
+        assert_eq!(Direction::Right.walk((2, 3), 7, (10, 10)), None);
+        assert_eq!(Direction::Down.walk((2, 3), usize::MAX, (10, 10)), None);
+    }
+
+    #[test]
+    fn test_find_word() {
+        let (input, line_size) = load_input(test_input(DAY, ""));
+        let grid = (input.as_slice(), line_size);
+        let matches = find_word(grid, "XMAS");
+        assert_eq!(matches.len(), 18);
+        assert_eq!(
+            matches[0],
+            Match {
+                word: 0,
+                start: (0, 4),
+                direction: Direction::DownRight
+            }
+        );
+        assert_eq!(
+            matches[..3]
+                .iter()
+                .map(|m| (m.start, m.direction))
+                .collect::<Vec<_>>(),
+            [
+                ((0, 4), Direction::DownRight),
+                ((0, 5), Direction::Right),
+                ((1, 4), Direction::Left)
+            ]
+        );
+        assert!(find_word(grid, "").is_empty());
+        assert!(find_word(grid, "XMASXMAS").is_empty());
+    }
+
+    #[test]
+    fn test_find_words() {
+        let grid: Vec<Vec<char>> = ["ABA", "BXB", "ABA"]
+            .iter()
+            .map(|line| line.chars().collect())
+            .collect();
+        let matches = find_words((&grid, 3), &["ABA", "X", "AXA", "BXB", "Q"]);
+        let count = |word| matches.iter().filter(|m| m.word == word).count();
+        assert_eq!(count(0), 8);
+        assert_eq!(count(1), 8);
+        assert_eq!(count(2), 4);
+        assert_eq!(count(3), 4);
+        assert_eq!(count(4), 0);
+        assert!(matches.windows(2).all(|w| w[0].start <= w[1].start));
+
+        let (input, line_size) = load_input(test_input(DAY, ""));
+        let grid = (input.as_slice(), line_size);
+        assert_eq!(find_words(grid, &["XMAS", "SAMX"]).len(), 36);
+        assert!(find_words((&[], 0), &["X"]).is_empty());
+    }
+}
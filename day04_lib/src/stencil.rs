@@ -0,0 +1,221 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use std::fmt::{self, Display, Formatter};
+use std::slice;
+
+const WILDCARD: char = '.';
+const ROW_SEPARATOR: char = '/';
+
+/// Small rectangular pattern of letters, `None` cells matching any letter.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Stencil {
+    rows: Vec<Vec<Option<char>>>,
+}
+
+/// Stencil placed with its top left corner at `top_left`, `stencil` being its
+/// index in the searched list.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Placement {
+    pub stencil: usize,
+    pub top_left: (usize, usize),
+}
+
+impl Stencil {
+    /// Parses rows separated by `/` with `.` as the wildcard, like
+    /// `M.S/.A./M.S`. Rows must be non-empty and of the same length.
+    pub fn parse(s: &str) -> Option<Self> {
+        let rows: Vec<Vec<_>> = s
+            .split(ROW_SEPARATOR)
+            .map(|row| row.chars().map(|c| (c != WILDCARD).then_some(c)).collect())
+            .collect();
+        let width = rows[0].len();
+        (width > 0 && rows.iter().all(|row| row.len() == width)).then_some(Self { rows })
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    /// Stencil turned a quarter clockwise.
+    pub fn rotate(&self) -> Self {
+        let rows = (0..self.width())
+            .map(|j| self.rows.iter().rev().map(|row| row[j]).collect())
+            .collect();
+        Self { rows }
+    }
+
+    /// Stencil mirrored left to right.
+    pub fn reflect(&self) -> Self {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| row.iter().rev().copied().collect())
+            .collect();
+        Self { rows }
+    }
+
+    /// Distinct stencils among the four rotations of this one and of its
+    /// reflection, starting with this one.
+    pub fn variants(&self) -> Vec<Self> {
+        let mut variants: Vec<Self> = vec![];
+        for mut stencil in [self.clone(), self.reflect()] {
+            for _ in 0..4 {
+                if !variants.contains(&stencil) {
+                    variants.push(stencil.clone());
+                }
+                stencil = stencil.rotate();
+            }
+        }
+        variants
+    }
+
+    pub fn matches_at(&self, grid: &[Vec<char>], (i, j): (usize, usize)) -> bool {
+        grid.len() >= i + self.height()
+            && self.rows.iter().zip(&grid[i..]).all(|(row, line)| {
+                line.len() >= j + row.len()
+                    && row
+                        .iter()
+                        .zip(&line[j..])
+                        .all(|(cell, letter)| cell.is_none_or(|c| c == *letter))
+            })
+    }
+}
+
+impl Display for Stencil {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{ROW_SEPARATOR}")?;
+            }
+            for cell in row {
+                write!(f, "{}", cell.unwrap_or(WILDCARD))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finds every placement of the stencils fitting inside the grid, in
+/// row-major order of the top left corner.
+pub fn find_stencils(
+    (grid, line_size): (&[Vec<char>], usize),
+    stencils: &[Stencil],
+) -> Vec<Placement> {
+    let mut placements = vec![];
+    for i in 0..grid.len() {
+        for j in 0..line_size {
+            for (index, stencil) in stencils.iter().enumerate() {
+                if stencil.matches_at(grid, (i, j)) {
+                    placements.push(Placement {
+                        stencil: index,
+                        top_left: (i, j),
+                    });
+                }
+            }
+        }
+    }
+    placements
+}
+
+/// Finds the placements of the stencil, or with `symmetric` of any of its
+/// [`Stencil::variants`], which placement indices then refer to.
+pub fn find_stencil(
+    grid: (&[Vec<char>], usize),
+    stencil: &Stencil,
+    symmetric: bool,
+) -> Vec<Placement> {
+    if symmetric {
+        find_stencils(grid, &stencil.variants())
+    } else {
+        find_stencils(grid, slice::from_ref(stencil))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_word, load_input, DAY};
+    use common::test_input;
+
+    fn stencil(s: &str) -> Stencil {
+        Stencil::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let x_mas = stencil("M.S/.A./M.S");
+        assert_eq!((x_mas.height(), x_mas.width()), (3, 3));
+        assert_eq!(x_mas.to_string(), "M.S/.A./M.S");
+        assert_eq!(stencil("XMAS").to_string(), "XMAS");
+        assert_eq!(Stencil::parse(""), None);
+        assert_eq!(Stencil::parse("M.S//M.S"), None);
+        assert_eq!(Stencil::parse("M.S/.A/M.S"), None);
+    }
+
+    #[test]
+    fn test_variants() {
+        let l = stencil("X./X./XX");
+        assert_eq!(l.rotate().to_string(), "XXX/X..");
+        assert_eq!(l.reflect().to_string(), ".X/.X/XX");
+        assert_eq!(l.rotate().rotate().rotate().rotate(), l);
+        let variants: Vec<_> = l.variants().iter().map(Stencil::to_string).collect();
+        assert_eq!(
+            variants,
+            [
+                "X./X./XX", "XXX/X..", "XX/.X/.X", "..X/XXX", ".X/.X/XX", "X../XXX", "XX/X./X.",
+                "XXX/..X"
+            ]
+        );
+        let x_mas: Vec<_> = stencil("M.S/.A./M.S")
+            .variants()
+            .iter()
+            .map(Stencil::to_string)
+            .collect();
+        assert_eq!(
+            x_mas,
+            ["M.S/.A./M.S", "M.M/.A./S.S", "S.M/.A./S.M", "S.S/.A./M.M"]
+        );
+        assert_eq!(stencil("A.A/.A./A.A").variants().len(), 1);
+    }
+
+    #[test]
+    fn test_find_stencil() {
+        let (input, line_size) = load_input(test_input(DAY, ""));
+        let grid = (input.as_slice(), line_size);
+        let x_mas = stencil("M.S/.A./M.S");
+        let placements = find_stencil(grid, &x_mas, true);
+        assert_eq!(placements.len(), 9);
+        assert_eq!(
+            placements[0],
+            Placement {
+                stencil: 0,
+                top_left: (0, 1)
+            }
+        );
+        assert_eq!(find_stencil(grid, &x_mas, false).len(), 2);
+
+        let row = find_stencil(grid, &stencil("XMAS"), true);
+        assert_eq!(row.len(), find_word(grid, "XMAS").len() - 10);
+        assert!(find_stencil(grid, &stencil("..........."), false).is_empty());
+        assert_eq!(find_stencil(grid, &stencil(".........."), false).len(), 10);
+        assert!(find_stencil((&[], 0), &x_mas, true).is_empty());
+    }
+}
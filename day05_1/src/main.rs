@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day05_lib::{is_right_order, load_input, middle, Input, DAY};
 
 fn sum_right_order_middles(
@@ -31,7 +31,14 @@ fn sum_right_order_middles(
 }
 
 fn main() {
-    println!("{}", sum_right_order_middles(load_input(input(DAY, ""))));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "1", &path, || {
+            let input = profile("parse", || load_input(&path));
+            profile("part 1", || sum_right_order_middles(input))
+        })
+    );
 }
 
 #[cfg(test)]
@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day05_lib::{is_right_order, load_input, middle, Input, DAY};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
@@ -53,13 +53,77 @@ fn sum_fix_order_middles(
 }
 
 fn main() {
-    println!("{}", sum_fix_order_middles(load_input(input(DAY, ""))));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "2", &path, || {
+            let input = profile("parse", || load_input(&path));
+            profile("part 2", || sum_fix_order_middles(input))
+        })
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use common::test_input;
+    use proptest::prelude::*;
+
+    fn permutations(pages: &[u32]) -> Vec<Vec<u32>> {
+        if pages.is_empty() {
+            return vec![vec![]];
+        }
+        (0..pages.len())
+            .flat_map(|i| {
+                let mut rest = pages.to_vec();
+                let page = rest.remove(i);
+                permutations(&rest).into_iter().map(move |mut tail| {
+                    tail.insert(0, page);
+                    tail
+                })
+            })
+            .collect()
+    }
+
+    fn fix_order_reference(pages: &[u32], ordering_rules: &HashMap<u32, HashSet<u32>>) -> Vec<u32> {
+        permutations(pages)
+            .into_iter()
+            .find(|pages| is_right_order(pages, ordering_rules))
+            .unwrap()
+    }
+
+    /// A random total order of distinct pages, as ordering rules, together
+    /// with an update listing the same pages shuffled.
+    fn rules_and_update() -> impl Strategy<Value = (HashMap<u32, HashSet<u32>>, Vec<u32>)> {
+        prop::collection::hash_set(10u32..100, 0..7)
+            .prop_map(Vec::from_iter)
+            .prop_flat_map(|pages| {
+                (
+                    Just(pages.clone()).prop_shuffle(),
+                    Just(pages).prop_shuffle(),
+                )
+            })
+            .prop_map(|(order, update)| {
+                let mut ordering_rules: HashMap<_, HashSet<_>> = HashMap::new();
+                for (i, &page) in order.iter().enumerate() {
+                    ordering_rules
+                        .entry(page)
+                        .or_default()
+                        .extend(&order[i + 1..]);
+                }
+                (ordering_rules, update)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_fix_order((ordering_rules, mut pages) in rules_and_update()) {
+            let expected = fix_order_reference(&pages, &ordering_rules);
+            fix_order(pages.as_mut_slice(), &ordering_rules);
+            prop_assert!(is_right_order(&pages, &ordering_rules));
+            prop_assert_eq!(pages, expected);
+        }
+    }
 
     #[test]
     fn test_fix_order() {
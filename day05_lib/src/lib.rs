@@ -16,7 +16,7 @@
 
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, ErrorKind};
 use std::path::Path;
 
 pub const DAY: &str = "05";
@@ -27,35 +27,47 @@ pub struct Input {
     pub pages: Vec<Vec<u32>>,
 }
 
-pub fn load_input(path: impl AsRef<Path>) -> Input {
-    let reader = BufReader::new(File::open(path).unwrap());
+fn invalid_line(line: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, format!("invalid line: {line:?}"))
+}
 
+pub fn parse_input(reader: impl BufRead) -> io::Result<Input> {
     let mut it = reader.lines();
 
     let mut ordering_rules: HashMap<_, HashSet<_>> = HashMap::new();
 
     loop {
-        let line = it.next().unwrap().unwrap();
+        let line = it.next().ok_or_else(|| {
+            io::Error::new(ErrorKind::UnexpectedEof, "missing empty line after rules")
+        })??;
         if line.is_empty() {
             break;
         }
-        let (n1, n2) = line.trim_end().split_once('|').unwrap();
-        let (n1, n2) = (n1.parse().unwrap(), n2.parse().unwrap());
+        let (n1, n2) = line
+            .trim_end()
+            .split_once('|')
+            .and_then(|(n1, n2)| Some((n1.parse().ok()?, n2.parse().ok()?)))
+            .ok_or_else(|| invalid_line(&line))?;
         ordering_rules.entry(n1).or_default().insert(n2);
     }
 
-    Input {
+    Ok(Input {
         ordering_rules,
         pages: it
-            .map_while(Result::ok)
             .map(|line| {
+                let line = line?;
                 line.trim_end()
                     .split(',')
-                    .map(|v| v.parse().unwrap())
-                    .collect()
+                    .map(|v| v.parse().ok())
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| invalid_line(&line))
             })
-            .collect(),
-    }
+            .collect::<io::Result<_>>()?,
+    })
+}
+
+pub fn load_input(path: impl AsRef<Path>) -> Input {
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
 }
 
 pub fn is_right_order(pages: &[u32], ordering_rules: &HashMap<u32, HashSet<u32>>) -> bool {
@@ -81,6 +93,21 @@ mod tests {
     use super::*;
     use common::test_input;
 
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input(&b"47|53\n\n75,47\n"[..]).unwrap(),
+            Input {
+                ordering_rules: HashMap::from([(47, HashSet::from([53]))]),
+                pages: vec![vec![75, 47]]
+            }
+        );
+        assert!(parse_input(&b""[..]).is_err());
+        assert!(parse_input(&b"47|53\n"[..]).is_err());
+        assert!(parse_input(&b"47-53\n\n"[..]).is_err());
+        assert!(parse_input(&b"47|53\n\n75,,47\n"[..]).is_err());
+    }
+
     #[test]
     fn test_load_input() {
         let input = load_input(test_input(DAY, ""));
@@ -14,10 +14,16 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day06_lib::{load_input, visited_points, DAY};
 
 fn main() {
-    let (map, guard_pos) = load_input(input(DAY, ""));
-    println!("{}", visited_points((&map, guard_pos)).len());
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "1", &path, || {
+            let (map, guard_pos) = profile("parse", || load_input(&path));
+            profile("part 1", || visited_points((&map, guard_pos)).len())
+        })
+    );
 }
@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day06_lib::{load_input, visited_points, Guard, ObstructionMap, DAY};
 use std::collections::HashSet;
 
@@ -51,7 +51,14 @@ fn count_loops((mut map, guard_pos): (ObstructionMap, (usize, usize))) -> usize
 }
 
 fn main() {
-    println!("{}", count_loops(load_input(input(DAY, ""))));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "2", &path, || {
+            let input = profile("parse", || load_input(&path));
+            profile("part 2", || count_loops(input))
+        })
+    );
 }
 
 #[cfg(test)]
@@ -17,7 +17,7 @@
 use std::cell::OnceCell;
 use std::collections::HashSet;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, ErrorKind};
 use std::path::Path;
 
 pub const DAY: &str = "06";
@@ -28,15 +28,16 @@ pub struct ObstructionMap {
     pub line_size: usize,
 }
 
-pub fn load_input(path: impl AsRef<Path>) -> (ObstructionMap, (usize, usize)) {
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+pub fn parse_input(reader: impl BufRead) -> io::Result<(ObstructionMap, (usize, usize))> {
     let mut map = vec![];
     let guard_position = OnceCell::new();
     let line_size = OnceCell::new();
-    for (i, line) in BufReader::new(File::open(path).unwrap())
-        .lines()
-        .enumerate()
-    {
-        let line = line.unwrap();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
         let mut row = vec![];
         if let Some(line_size) = line_size.get() {
             row.reserve(*line_size);
@@ -44,20 +45,31 @@ pub fn load_input(path: impl AsRef<Path>) -> (ObstructionMap, (usize, usize)) {
         for (j, ch) in line.chars().enumerate() {
             row.push(ch == '#');
             if ch == '^' {
-                guard_position.set((i, j)).unwrap();
+                guard_position
+                    .set((i, j))
+                    .map_err(|_| invalid_data("more than one guard"))?;
             }
         }
-        assert_eq!(line_size.get_or_init(|| row.len()), &row.len());
+        if line_size.get_or_init(|| row.len()) != &row.len() {
+            return Err(invalid_data("lines have different lengths"));
+        }
         map.push(row);
     }
 
-    (
+    Ok((
         ObstructionMap {
             map,
-            line_size: line_size.get().copied().unwrap(),
+            line_size: line_size.get().copied().unwrap_or_default(),
         },
-        guard_position.get().copied().unwrap(),
-    )
+        guard_position
+            .get()
+            .copied()
+            .ok_or_else(|| invalid_data("no guard"))?,
+    ))
+}
+
+pub fn load_input(path: impl AsRef<Path>) -> (ObstructionMap, (usize, usize)) {
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
 }
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone)]
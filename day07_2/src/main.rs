@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day07_lib::{load_input, total_sum, Eval, DAY};
 use strum_macros::EnumIter;
 
@@ -43,7 +43,14 @@ impl Eval for Op {
 }
 
 fn main() {
-    println!("{}", total_sum::<Op>(load_input(input(DAY, "")).as_slice()));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "2", &path, || {
+            let input = profile("parse", || load_input(&path));
+            profile("part 2", || total_sum::<Op>(input.as_slice()))
+        })
+    );
 }
 
 #[cfg(test)]
@@ -15,29 +15,38 @@
 */
 
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, ErrorKind};
 use std::path::Path;
 use strum::IntoEnumIterator;
 
 pub const DAY: &str = "07";
 
-pub fn load_input(path: impl AsRef<Path>) -> Vec<(u64, Vec<u32>)> {
-    let input = BufReader::new(File::open(path).unwrap());
-    input
+fn parse_line(line: &str) -> Option<(u64, Vec<u32>)> {
+    let (result, args) = line.split_once(':')?;
+    Some((
+        result.parse().ok()?,
+        args.split_whitespace()
+            .map(|num| num.parse().ok())
+            .collect::<Option<_>>()?,
+    ))
+}
+
+pub fn parse_input(reader: impl BufRead) -> io::Result<Vec<(u64, Vec<u32>)>> {
+    reader
         .lines()
-        .map(|l| l.unwrap())
         .map(|line| {
-            let (result, args) = line.split_once(':').unwrap();
-            (
-                result.parse().unwrap(),
-                args.split_whitespace()
-                    .map(|num| num.parse().unwrap())
-                    .collect(),
-            )
+            let line = line?;
+            parse_line(line.as_str()).ok_or_else(|| {
+                io::Error::new(ErrorKind::InvalidData, format!("invalid line: {line:?}"))
+            })
         })
         .collect()
 }
 
+pub fn load_input(path: impl AsRef<Path>) -> Vec<(u64, Vec<u32>)> {
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
+}
+
 pub trait Eval {
     fn eval(&self, left: u64, right: u32) -> Option<u64>;
 }
@@ -79,6 +88,15 @@ mod tests {
     use super::*;
     use common::test_input;
 
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(parse_line("190: 10 19"), Some((190, vec![10, 19])));
+        assert_eq!(parse_line("190:"), Some((190, vec![])));
+        assert_eq!(parse_line("190 10 19"), None);
+        assert_eq!(parse_line("190: 10 x"), None);
+        assert_eq!(parse_line("-190: 10"), None);
+    }
+
     #[test]
     fn test_load_input() {
         let input = load_input(test_input(DAY, ""));
@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day08_lib::{antinodes_count, load_input, signed_overflowing_sub, DAY};
 use tinyvec::ArrayVec;
 
@@ -46,7 +46,14 @@ fn antinodes(
 }
 
 fn main() {
-    println!("{}", antinodes_count(load_input(input(DAY, "")), antinodes));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "1", &path, || {
+            let input = profile("parse", || load_input(&path));
+            profile("part 1", || antinodes_count(input, antinodes))
+        })
+    );
 }
 
 #[cfg(test)]
@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day08_lib::{antinodes_count, load_input, signed_overflowing_sub, DAY};
 
 fn antinodes(
@@ -45,15 +45,63 @@ fn antinodes(
 }
 
 fn main() {
-    println!("{}", antinodes_count(load_input(input(DAY, "")), antinodes));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "2", &path, || {
+            let input = profile("parse", || load_input(&path));
+            profile("part 2", || antinodes_count(input, antinodes))
+        })
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use common::test_input;
+    use proptest::prelude::*;
     use std::collections::HashSet;
 
+    fn antinodes_reference(
+        [(i1, j1), (i2, j2)]: [(usize, usize); 2],
+        (height, width): (usize, usize),
+    ) -> HashSet<(usize, usize)> {
+        let (i1, j1, i2, j2) = (i1 as isize, j1 as isize, i2 as isize, j2 as isize);
+        let (diff_i, diff_j) = (i2 - i1, j2 - j1);
+        let on_line = |i: isize, j: isize| {
+            let (di, dj) = (i - i1, j - j1);
+            di * diff_j == dj * diff_i
+                && if diff_i != 0 {
+                    di % diff_i == 0
+                } else {
+                    dj % diff_j == 0
+                }
+        };
+        (0..height)
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .filter(|&(i, j)| on_line(i as isize, j as isize))
+            .collect()
+    }
+
+    fn antenna_pair() -> impl Strategy<Value = ([(usize, usize); 2], (usize, usize))> {
+        (1..20usize, 1..20usize)
+            .prop_flat_map(|(height, width)| {
+                let antenna = (0..height, 0..width);
+                ([antenna.clone(), antenna], Just((height, width)))
+            })
+            .prop_filter("antennas must differ", |([a1, a2], _)| a1 != a2)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_antinodes((antennas, size) in antenna_pair()) {
+            prop_assert_eq!(
+                HashSet::from_iter(antinodes(antennas, size)),
+                antinodes_reference(antennas, size)
+            );
+        }
+    }
+
     #[test]
     fn test_antinodes() {
         assert_eq!(
@@ -18,7 +18,7 @@ use std::cell::OnceCell;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::hash::RandomState;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, ErrorKind};
 use std::path::Path;
 
 pub const DAY: &str = "08";
@@ -29,13 +29,13 @@ pub struct Input {
     pub size: (usize, usize),
 }
 
-pub fn load_input(path: impl AsRef<Path>) -> Input {
+pub fn parse_input(reader: impl BufRead) -> io::Result<Input> {
     let mut antennas: HashMap<_, HashSet<_>> = HashMap::new();
     let width = OnceCell::new();
     let mut i = 0;
 
-    for line in BufReader::new(File::open(path).unwrap()).lines() {
-        let line = line.unwrap();
+    for line in reader.lines() {
+        let line = line?;
         let mut j = 0;
         for ch in line.chars() {
             if matches!(ch, 'A'..='Z' | 'a'..='z' | '0'..='9') {
@@ -43,14 +43,23 @@ pub fn load_input(path: impl AsRef<Path>) -> Input {
             }
             j += 1;
         }
-        assert_eq!(width.get_or_init(|| j), &j);
+        if width.get_or_init(|| j) != &j {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("line {i} has different length"),
+            ));
+        }
         i += 1;
     }
 
-    Input {
+    Ok(Input {
         antennas,
         size: (i, width.get().copied().unwrap_or_default()),
-    }
+    })
+}
+
+pub fn load_input(path: impl AsRef<Path>) -> Input {
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
 }
 
 pub fn signed_overflowing_sub(n1: usize, n2: usize) -> isize {
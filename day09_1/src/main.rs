@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day09_lib::{checksum, load_input, DAY};
 
 fn defrag<T>(mut disk_map: &mut [Option<T>]) {
@@ -37,15 +37,47 @@ fn defrag<T>(mut disk_map: &mut [Option<T>]) {
 }
 
 fn main() {
-    let mut disk_map = load_input(input(DAY, ""));
-    defrag(&mut disk_map);
-    println!("{}", checksum(disk_map.as_slice()));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "1", &path, || {
+            let mut disk_map = profile("parse", || load_input(&path));
+            profile("part 1", || {
+                defrag(&mut disk_map);
+                checksum(disk_map.as_slice())
+            })
+        })
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use common::test_input;
+    use proptest::prelude::*;
+
+    fn defrag_reference<T>(disk_map: &mut [Option<T>]) {
+        while let (Some(empty), Some(file)) = (
+            disk_map.iter().position(|block| block.is_none()),
+            disk_map.iter().rposition(|block| block.is_some()),
+        ) {
+            if file < empty {
+                break;
+            }
+            disk_map.swap(empty, file);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_defrag(disk_map in prop::collection::vec(prop::option::of(0u32..10), 0..64)) {
+            let mut expected = disk_map.clone();
+            defrag_reference(&mut expected);
+            let mut actual = disk_map;
+            defrag(&mut actual);
+            prop_assert_eq!(actual, expected);
+        }
+    }
 
     #[test]
     fn test_defrag() {
@@ -14,7 +14,7 @@
   limitations under the License.
 */
 
-use common::input;
+use common::{cached, input, profile};
 use day09_lib::{checksum, load_input, DAY};
 use std::collections::BTreeSet;
 
@@ -59,9 +59,17 @@ fn defrag<T: Copy + Eq>(mut disk_map: &mut [Option<T>]) {
 }
 
 fn main() {
-    let mut disk_map = load_input(input(DAY, ""));
-    defrag(&mut disk_map);
-    println!("{}", checksum(disk_map.as_slice()));
+    let path = input(DAY, "");
+    println!(
+        "{}",
+        cached(DAY, "2", &path, || {
+            let mut disk_map = profile("parse", || load_input(&path));
+            profile("part 2", || {
+                defrag(&mut disk_map);
+                checksum(disk_map.as_slice())
+            })
+        })
+    );
 }
 
 #[cfg(test)]
@@ -15,18 +15,16 @@
 */
 
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{self, BufReader, Read};
 use std::path::Path;
 
 pub const DAY: &str = "09";
 
-pub fn load_input(path: impl AsRef<Path>) -> Vec<Option<u32>> {
+pub fn parse_input(mut reader: impl Read) -> io::Result<Vec<Option<u32>>> {
     let mut content = String::new();
-    BufReader::new(File::open(path).unwrap())
-        .read_to_string(&mut content)
-        .unwrap();
+    reader.read_to_string(&mut content)?;
 
-    content
+    Ok(content
         .chars()
         .map_while(|c| c.to_digit(10))
         .scan((true, 0), |(is_file, id), size| {
@@ -42,7 +40,11 @@ pub fn load_input(path: impl AsRef<Path>) -> Vec<Option<u32>> {
             Some((result, size))
         })
         .flat_map(|(result, size)| (0..size).map(move |_| result))
-        .collect()
+        .collect())
+}
+
+pub fn load_input(path: impl AsRef<Path>) -> Vec<Option<u32>> {
+    parse_input(BufReader::new(File::open(path).unwrap())).unwrap()
 }
 
 pub fn checksum(disk_map: &[Option<u32>]) -> usize {
@@ -0,0 +1,35 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day02_lib::parse_input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(rows) = parse_input(data) {
+        assert!(rows.iter().all(|row| !row.is_empty()));
+        assert!(rows.iter().map(Vec::len).sum::<usize>() <= data.len());
+        let text: String = rows
+            .iter()
+            .map(|row| {
+                let row: Vec<_> = row.iter().map(u32::to_string).collect();
+                row.join(" ") + "\n"
+            })
+            .collect();
+        assert_eq!(parse_input(text.as_bytes()).unwrap(), rows);
+    }
+});
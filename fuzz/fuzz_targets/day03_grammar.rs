@@ -0,0 +1,114 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day03_lib::{Definition, Grammar, Instruction, Interpreter};
+use libfuzzer_sys::fuzz_target;
+use regex::bytes::Regex;
+use std::io::ErrorKind;
+
+/// Reference for the scanner: a regex of every registered instruction, with
+/// the delimiters of the grammar.
+fn reference(interpreter: &Interpreter, grammar: &Grammar, data: &[u8]) -> Vec<Instruction> {
+    let space = if grammar.whitespace {
+        r"[ \t\n\x0C\r]{0,64}"
+    } else {
+        ""
+    };
+    let sign = if grammar.signed { "[+-]?" } else { "" };
+    let operand = format!(
+        r"{space}{sign}[0-9]{{{},{}}}{space}",
+        grammar.min_digits, grammar.max_digits
+    );
+    let [open, separator, close] = [grammar.open, grammar.separator, grammar.close]
+        .map(|delimiter| regex::escape(&char::from(delimiter).to_string()));
+    let alternatives: Vec<_> = interpreter
+        .definitions()
+        .iter()
+        .map(|d| {
+            let operands = match d.arity {
+                0 => space.to_string(),
+                arity => vec![operand.as_str(); arity].join(&separator),
+            };
+            format!("{}{open}{operands}{close}", regex::escape(d.name))
+        })
+        .collect();
+    let regex = Regex::new(&format!("(?-u){}", alternatives.join("|"))).unwrap();
+
+    regex
+        .find_iter(data)
+        .map(|m| {
+            let text = std::str::from_utf8(m.as_bytes()).unwrap();
+            let name = interpreter
+                .definitions()
+                .iter()
+                .map(|d| d.name)
+                .find(|name| text.starts_with(name) && text.as_bytes()[name.len()] == grammar.open)
+                .unwrap();
+            let inner = &text[name.len() + 1..text.len() - 1];
+            let args = inner
+                .split(char::from(grammar.separator))
+                .filter(|_| !inner.trim().is_empty())
+                .map(|arg| arg.trim().parse().unwrap())
+                .collect();
+            Instruction {
+                name,
+                args,
+                span: m.range(),
+            }
+        })
+        .collect()
+}
+
+/// Delimiters the regex reference can tell apart from names and operands.
+fn is_plain_delimiter(byte: u8) -> bool {
+    byte.is_ascii_punctuation() && !b"+-'".contains(&byte)
+}
+
+fuzz_target!(|input: ((u8, u8, bool, bool), [u8; 3], Vec<u8>)| {
+    let ((min_digits, max_digits, signed, whitespace), [open, separator, close], data) = input;
+    let grammar = Grammar {
+        min_digits: min_digits % 20,
+        max_digits: max_digits % 20,
+        signed,
+        whitespace,
+        open,
+        separator,
+        close,
+    };
+    let mut interpreter = Interpreter::part_two();
+    interpreter
+        .register(Definition::ADD)
+        .register(Definition::SUB)
+        .register(Definition::RESET);
+    if interpreter.set_grammar(grammar).is_err() {
+        assert!(grammar.validate().is_err());
+        return;
+    }
+
+    let instructions = interpreter.scanner().parse(data.as_slice()).unwrap();
+    if [open, separator, close].into_iter().all(is_plain_delimiter) {
+        assert_eq!(instructions, reference(&interpreter, &grammar, &data));
+    }
+    match interpreter.run_reader(data.as_slice()) {
+        Ok(machine) => assert_eq!(interpreter.run(&instructions), Ok(machine)),
+        Err(err) => {
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+            assert!(interpreter.run(&instructions).is_err());
+        }
+    }
+});
@@ -0,0 +1,40 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day03_lib::{parse_instructions, Instruction};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let instructions = parse_instructions(data).unwrap();
+    let mut end = 0;
+    for instruction in &instructions {
+        let Instruction { name, args, span } = instruction;
+        assert!(end <= span.start && span.end <= data.len());
+        end = span.end;
+        let args: Vec<_> = args.iter().map(i64::to_string).collect();
+        let text = format!("{name}({})", args.join(","));
+        assert!(text.len() <= span.len());
+        assert_eq!(
+            parse_instructions(&data[span.clone()]).unwrap(),
+            [Instruction {
+                span: 0..span.len(),
+                ..instruction.clone()
+            }]
+        );
+    }
+});
@@ -0,0 +1,37 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day03_lib::{scan, Scanner};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Vec<u8>, Vec<u8>)| {
+    let (data, splits) = input;
+    let mut expected = vec![];
+    scan(data.as_slice(), |instruction| expected.push(instruction)).unwrap();
+
+    let mut scanner = Scanner::default();
+    let mut instructions = vec![];
+    let mut rest = data.as_slice();
+    for &split in &splits {
+        let (chunk, tail) = rest.split_at((split as usize).min(rest.len()));
+        scanner.feed(chunk, |instruction| instructions.push(instruction));
+        rest = tail;
+    }
+    scanner.feed(rest, |instruction| instructions.push(instruction));
+    assert_eq!(instructions, expected);
+});
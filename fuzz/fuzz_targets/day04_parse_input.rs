@@ -0,0 +1,32 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day04_lib::parse_input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((rows, line_size)) = parse_input(data) {
+        assert!(rows.iter().all(|row| row.len() == line_size));
+        assert!(rows.len() * line_size <= data.len());
+        let text: String = rows
+            .iter()
+            .map(|row| row.iter().chain(['\n'].iter()).collect::<String>())
+            .collect();
+        assert_eq!(parse_input(text.as_bytes()).unwrap(), (rows, line_size));
+    }
+});
@@ -0,0 +1,42 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day05_lib::{parse_input, Input};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = parse_input(data) {
+        let mut text = String::new();
+        for (page, later_pages) in &input.ordering_rules {
+            for later_page in later_pages {
+                text += &format!("{page}|{later_page}\n");
+            }
+        }
+        text.push('\n');
+        for pages in &input.pages {
+            let pages: Vec<_> = pages.iter().map(u32::to_string).collect();
+            text += &(pages.join(",") + "\n");
+        }
+        let Input {
+            ordering_rules,
+            pages,
+        } = parse_input(text.as_bytes()).unwrap();
+        assert_eq!(ordering_rules, input.ordering_rules);
+        assert_eq!(pages, input.pages);
+    }
+});
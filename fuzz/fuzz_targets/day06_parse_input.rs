@@ -0,0 +1,44 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day06_lib::parse_input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((map, (gi, gj))) = parse_input(data) {
+        assert!(map.map.iter().all(|row| row.len() == map.line_size));
+        assert!(map.map.len() * map.line_size <= data.len());
+        assert!(!map.map[gi][gj]);
+        let text: String = map
+            .map
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(j, &obstruction)| match obstruction {
+                        _ if (i, j) == (gi, gj) => '^',
+                        true => '#',
+                        false => '.',
+                    })
+                    .chain(['\n'])
+            })
+            .collect();
+        assert_eq!(parse_input(text.as_bytes()).unwrap(), (map, (gi, gj)));
+    }
+});
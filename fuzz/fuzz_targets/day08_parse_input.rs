@@ -0,0 +1,41 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day08_lib::{parse_input, Input};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = parse_input(data) {
+        let Input {
+            ref antennas,
+            size: (height, width),
+        } = input;
+        assert!(height * width <= data.len());
+        let mut grid = vec![vec!['.'; width]; height];
+        for (&frequency, antennas) in antennas {
+            for &(i, j) in antennas {
+                grid[i][j] = frequency;
+            }
+        }
+        let text: String = grid
+            .iter()
+            .flat_map(|row| row.iter().chain(['\n'].iter()))
+            .collect();
+        assert_eq!(parse_input(text.as_bytes()).unwrap(), input);
+    }
+});
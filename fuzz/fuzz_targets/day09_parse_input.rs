@@ -0,0 +1,76 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+#![no_main]
+
+use day09_lib::parse_input;
+use libfuzzer_sys::fuzz_target;
+
+fn push_digit(digits: &mut String, n: usize) {
+    digits.push(char::from_digit(n as u32, 10).unwrap());
+}
+
+/// Free space of `free` blocks spread over `slots` digits, separated by
+/// empty files.
+fn push_free_space(digits: &mut String, mut free: usize, slots: usize) {
+    for slot in 0..slots {
+        if slot > 0 {
+            digits.push('0');
+        }
+        let size = free.min(9);
+        push_digit(digits, size);
+        free -= size;
+    }
+    assert_eq!(free, 0);
+}
+
+fn unparse(mut disk_map: &[Option<u32>]) -> String {
+    let mut digits = String::new();
+    let mut id = 0;
+    loop {
+        let file = disk_map
+            .iter()
+            .take_while(|&&block| block == Some(id))
+            .count();
+        push_digit(&mut digits, file);
+        disk_map = &disk_map[file..];
+        let free = disk_map.iter().take_while(|block| block.is_none()).count();
+        disk_map = &disk_map[free..];
+        match disk_map.first() {
+            Some(&Some(next_id)) => {
+                push_free_space(&mut digits, free, (next_id - id) as usize);
+                id = next_id;
+            }
+            Some(None) => unreachable!(),
+            None => {
+                if free > 0 {
+                    push_free_space(&mut digits, free, free.div_ceil(9));
+                }
+                return digits;
+            }
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(disk_map) = parse_input(data) {
+        assert!(disk_map.len() <= 9 * data.len());
+        assert_eq!(
+            parse_input(unparse(&disk_map).as_bytes()).unwrap(),
+            disk_map
+        );
+    }
+});
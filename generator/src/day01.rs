@@ -0,0 +1,56 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::Rng;
+use std::io::{self, Write};
+
+/// `pairs` lines of two five-digit location IDs.
+pub fn generate(out: &mut impl Write, rng: &mut impl Rng, pairs: usize) -> io::Result<()> {
+    for _ in 0..pairs {
+        let a: u32 = rng.gen_range(10000..100000);
+        let b: u32 = rng.gen_range(10000..100000);
+        writeln!(out, "{a}   {b}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day01_lib::parse_input;
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(1), 1000).unwrap();
+        let (a, b) = parse_input(out.as_slice()).unwrap();
+        assert_eq!(a.len(), 1000);
+        assert_eq!(b.len(), 1000);
+        assert!(a.iter().chain(&b).all(|id| (10000..100000).contains(id)));
+    }
+
+    #[test]
+    fn test_seed() {
+        let generated = |seed| {
+            let mut out = vec![];
+            generate(&mut out, &mut rng(seed), 100).unwrap();
+            out
+        };
+        assert_eq!(generated(7), generated(7));
+        assert_ne!(generated(7), generated(8));
+    }
+}
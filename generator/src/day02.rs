@@ -0,0 +1,86 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::Rng;
+use std::io::{self, Write};
+
+fn step(rng: &mut impl Rng, increasing: bool, bad_probability: f64) -> i64 {
+    let step = match (rng.gen_bool(bad_probability), rng.gen_range(0..3)) {
+        (false, _) => rng.gen_range(1..=3),
+        (true, 0) => 0,
+        (true, 1) => rng.gen_range(4..=9),
+        (true, _) => -rng.gen_range(1..=3),
+    };
+    if increasing {
+        step
+    } else {
+        -step
+    }
+}
+
+/// `reports` lines of `levels` levels each. Reports are monotone with steps of
+/// `1..=3` except for about one bad step (flat, too large or reversed) per report.
+pub fn generate(
+    out: &mut impl Write,
+    rng: &mut impl Rng,
+    reports: usize,
+    levels: usize,
+) -> io::Result<()> {
+    let bad_probability = 1.0 / levels.max(1) as f64;
+    for _ in 0..reports {
+        let increasing = rng.gen_bool(0.5);
+        let mut level = rng.gen_range(1..100) + if increasing { 0 } else { 3 * levels as i64 };
+        for i in 0..levels {
+            if i > 0 {
+                out.write_all(b" ")?;
+                level = (level + step(rng, increasing, bad_probability)).max(0);
+            }
+            write!(out, "{level}")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day02_lib::{is_safe_report, parse_input, SafetyPolicy};
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(2), 1000, 8).unwrap();
+        let rows = parse_input(out.as_slice()).unwrap();
+        assert_eq!(rows.len(), 1000);
+        assert!(rows.iter().all(|row| row.len() == 8));
+        let safe = rows
+            .iter()
+            .filter(|row| is_safe_report(row, &SafetyPolicy::default()))
+            .count();
+        assert!(safe > 0 && safe < rows.len(), "safe = {safe}");
+    }
+
+    #[test]
+    fn test_generate_long() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(2), 3, 100_000).unwrap();
+        let rows = parse_input(out.as_slice()).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.len() == 100_000));
+    }
+}
@@ -0,0 +1,71 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io::{self, Write};
+
+const NOISE: &[u8] = b"!@#$%^&*()[]{}<>?+-_';:,. \nxmuldontwhatfromselectwhohowwhere";
+
+fn chunk(rng: &mut impl Rng) -> Vec<u8> {
+    match rng.gen_range(0..10) {
+        0..=2 => format!("mul({},{})", rng.gen_range(0..1000), rng.gen_range(0..1000)).into(),
+        3 => b"do()".to_vec(),
+        4 => b"don't()".to_vec(),
+        5 => {
+            let (n1, n2) = (rng.gen_range(0..10000), rng.gen_range(0..1000));
+            match rng.gen_range(0..4) {
+                0 => format!("mul({n1},{n2}]"),
+                1 => format!("mul[{n1},{n2}]"),
+                2 => format!("mul ( {n1},{n2})"),
+                _ => format!("mul({n1}{n2})"),
+            }
+            .into()
+        }
+        _ => (0..rng.gen_range(1..16))
+            .map(|_| *NOISE.choose(rng).unwrap())
+            .collect(),
+    }
+}
+
+/// About `bytes` bytes of corrupted memory mixing `mul`, `do` and `don't`
+/// instructions, near misses and noise.
+pub fn generate(out: &mut impl Write, rng: &mut impl Rng, bytes: usize) -> io::Result<()> {
+    let mut written = 0;
+    while written < bytes {
+        let chunk = chunk(rng);
+        out.write_all(&chunk)?;
+        written += chunk.len();
+    }
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day03_lib::parse_input;
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(3), 10000).unwrap();
+        let input = parse_input(out.as_slice()).unwrap();
+        assert!((10000..10100).contains(&input.len()));
+        assert!(input.windows(4).any(|w| w == b"mul("));
+        assert!(input.windows(7).any(|w| w == b"don't()"));
+    }
+}
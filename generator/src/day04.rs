@@ -0,0 +1,53 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io::{self, Write};
+
+/// `height` lines of `width` letters from `XMAS`.
+pub fn generate(
+    out: &mut impl Write,
+    rng: &mut impl Rng,
+    height: usize,
+    width: usize,
+) -> io::Result<()> {
+    let mut line = Vec::with_capacity(width + 1);
+    for _ in 0..height {
+        line.clear();
+        line.extend((0..width).map(|_| *b"XMAS".choose(rng).unwrap()));
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day04_lib::parse_input;
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(4), 30, 50).unwrap();
+        let (input, line_size) = parse_input(out.as_slice()).unwrap();
+        assert_eq!(input.len(), 30);
+        assert_eq!(line_size, 50);
+        assert!(input.iter().flatten().all(|ch| "XMAS".contains(*ch)));
+    }
+}
@@ -0,0 +1,88 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+use std::io::{self, Write};
+
+/// Ordering rules for every pair of `pages` distinct page numbers (so that any
+/// update can be fixed), followed by `updates` updates of odd length, about
+/// half of them in the right order.
+pub fn generate(
+    out: &mut impl Write,
+    rng: &mut impl Rng,
+    pages: usize,
+    updates: usize,
+) -> io::Result<()> {
+    assert!(pages > 0 || updates == 0, "updates need pages");
+    let upper = 100.max(10 + 10 * pages);
+    let order: Vec<_> = index::sample(rng, upper - 10, pages)
+        .into_iter()
+        .map(|page| page + 10)
+        .collect();
+
+    let mut rules: Vec<_> = (0..pages)
+        .flat_map(|i| (i + 1..pages).map(move |j| (i, j)))
+        .collect();
+    rules.shuffle(rng);
+    for (i, j) in rules {
+        writeln!(out, "{}|{}", order[i], order[j])?;
+    }
+    writeln!(out)?;
+
+    let max_len = pages.min(23);
+    for _ in 0..updates {
+        let len = rng.gen_range(0..=(max_len - 1) / 2) * 2 + 1;
+        let mut update = index::sample(rng, pages, len).into_vec();
+        if rng.gen_bool(0.5) {
+            update.sort_unstable();
+        }
+        let update: Vec<_> = update.into_iter().map(|i| order[i].to_string()).collect();
+        writeln!(out, "{}", update.join(","))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day05_lib::{is_right_order, parse_input, Input};
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(5), 40, 200).unwrap();
+        let Input {
+            ordering_rules,
+            pages,
+        } = parse_input(out.as_slice()).unwrap();
+        assert_eq!(
+            ordering_rules
+                .values()
+                .map(|later| later.len())
+                .sum::<usize>(),
+            780
+        );
+        assert_eq!(pages.len(), 200);
+        assert!(pages.iter().all(|pages| pages.len() % 2 == 1));
+        let right = pages
+            .iter()
+            .filter(|pages| is_right_order(pages, &ordering_rules))
+            .count();
+        assert!(right > 0 && right < pages.len(), "right = {right}");
+    }
+}
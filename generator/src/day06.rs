@@ -0,0 +1,104 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::Rng;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+fn next_point(
+    (i, j): (usize, usize),
+    direction: usize,
+    (height, width): (usize, usize),
+) -> Option<(usize, usize)> {
+    let (di, dj) = DIRECTIONS[direction];
+    let i = i.checked_add_signed(di).filter(|&i| i < height)?;
+    let j = j.checked_add_signed(dj).filter(|&j| j < width)?;
+    Some((i, j))
+}
+
+/// Obstruction the guard bumps into when it closes a loop, if it does.
+fn loop_obstruction(map: &[Vec<bool>], start: (usize, usize)) -> Option<(usize, usize)> {
+    let size = (map.len(), map[0].len());
+    let mut turning_points = HashSet::new();
+    let (mut pos, mut direction) = (start, 0);
+    loop {
+        let next = next_point(pos, direction, size)?;
+        if map[next.0][next.1] {
+            if !turning_points.insert((pos, direction)) {
+                return Some(next);
+            }
+            direction = (direction + 1) % DIRECTIONS.len();
+        } else {
+            pos = next;
+        }
+    }
+}
+
+/// `height` lines of `width` cells with about 5% obstructions and a guard
+/// facing up. Obstructions are removed until the guard's patrol leaves the map.
+pub fn generate(
+    out: &mut impl Write,
+    rng: &mut impl Rng,
+    height: usize,
+    width: usize,
+) -> io::Result<()> {
+    assert!(height > 0 && width > 0, "the guard needs a cell");
+    let mut map: Vec<Vec<_>> = (0..height)
+        .map(|_| (0..width).map(|_| rng.gen_bool(0.05)).collect())
+        .collect();
+    let guard @ (gi, gj) = (rng.gen_range(0..height), rng.gen_range(0..width));
+    map[gi][gj] = false;
+    while let Some((i, j)) = loop_obstruction(&map, guard) {
+        map[i][j] = false;
+    }
+
+    let mut line = Vec::with_capacity(width + 1);
+    for (i, row) in map.iter().enumerate() {
+        line.clear();
+        line.extend(
+            row.iter()
+                .map(|&obstruction| if obstruction { b'#' } else { b'.' }),
+        );
+        if i == gi {
+            line[gj] = b'^';
+        }
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day06_lib::{parse_input, visited_points};
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let mut out = vec![];
+            generate(&mut out, &mut rng(seed), 40, 60).unwrap();
+            let (map, guard_pos) = parse_input(out.as_slice()).unwrap();
+            assert_eq!(map.map.len(), 40);
+            assert_eq!(map.line_size, 60);
+            assert!(!map.map[guard_pos.0][guard_pos.1]);
+            assert!(!visited_points((&map, guard_pos)).is_empty());
+        }
+    }
+}
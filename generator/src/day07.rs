@@ -0,0 +1,84 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::Rng;
+use std::io::{self, Write};
+
+fn concat(left: u64, right: u64) -> Option<u64> {
+    10u64
+        .checked_pow(1 + right.checked_ilog10().unwrap_or(0))
+        .and_then(|n| left.checked_mul(n))
+        .and_then(|n| n.checked_add(right))
+}
+
+/// `equations` lines of `operands` operands in `1..1000`. About two thirds of
+/// the test values are produced by a random chain of `+`, `*` and `||`; the
+/// rest are random and usually unreachable.
+pub fn generate(
+    out: &mut impl Write,
+    rng: &mut impl Rng,
+    equations: usize,
+    operands: usize,
+) -> io::Result<()> {
+    let mut args = Vec::with_capacity(operands);
+    for _ in 0..equations {
+        args.clear();
+        args.extend((0..operands).map(|_| rng.gen_range(1..1000u64)));
+        let test_value = if rng.gen_ratio(2, 3) {
+            args.iter()
+                .copied()
+                .reduce(|left, right| {
+                    match rng.gen_range(0..3) {
+                        0 => left.checked_mul(right),
+                        1 => concat(left, right),
+                        _ => None,
+                    }
+                    .unwrap_or(left.saturating_add(right))
+                })
+                .unwrap_or_default()
+        } else {
+            rng.gen_range(1..u64::MAX >> 20)
+        };
+        write!(out, "{test_value}:")?;
+        for arg in &args {
+            write!(out, " {arg}")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day07_lib::parse_input;
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(7), 100, 12).unwrap();
+        let input = parse_input(out.as_slice()).unwrap();
+        assert_eq!(input.len(), 100);
+        assert!(input.iter().all(|(_, args)| args.len() == 12));
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(u64::MAX, 1), None);
+    }
+}
@@ -0,0 +1,61 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::io::{self, Write};
+
+const FREQUENCIES: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// `height` lines of `width` cells with antennas of random frequencies on
+/// about 4% of them.
+pub fn generate(
+    out: &mut impl Write,
+    rng: &mut impl Rng,
+    height: usize,
+    width: usize,
+) -> io::Result<()> {
+    let mut line = Vec::with_capacity(width + 1);
+    for _ in 0..height {
+        line.clear();
+        line.extend((0..width).map(|_| {
+            if rng.gen_ratio(1, 25) {
+                *FREQUENCIES.choose(rng).unwrap()
+            } else {
+                b'.'
+            }
+        }));
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day08_lib::{parse_input, Input};
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(8), 50, 70).unwrap();
+        let Input { antennas, size } = parse_input(out.as_slice()).unwrap();
+        assert_eq!(size, (50, 70));
+        assert!(antennas.values().any(|antennas| antennas.len() > 1));
+    }
+}
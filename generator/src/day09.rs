@@ -0,0 +1,53 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use rand::Rng;
+use std::io::{self, Write};
+
+const CHUNK: usize = 1 << 16;
+
+/// Disk map of `digits` digits alternating file sizes in `1..=9` and free
+/// space sizes in `0..=9`.
+pub fn generate(out: &mut impl Write, rng: &mut impl Rng, digits: usize) -> io::Result<()> {
+    let mut buffer = Vec::with_capacity(digits.min(CHUNK));
+    for i in 0..digits {
+        let min = if i % 2 == 0 { 1 } else { 0 };
+        buffer.push(b'0' + rng.gen_range(min..=9));
+        if buffer.len() == CHUNK {
+            out.write_all(&buffer)?;
+            buffer.clear();
+        }
+    }
+    out.write_all(&buffer)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng;
+    use day09_lib::parse_input;
+
+    #[test]
+    fn test_generate() {
+        let mut out = vec![];
+        generate(&mut out, &mut rng(9), 1001).unwrap();
+        let disk_map = parse_input(out.as_slice()).unwrap();
+        assert!((501..=9 * 1001).contains(&disk_map.len()));
+        assert_eq!(disk_map.first(), Some(&Some(0)));
+        assert_eq!(disk_map.iter().flatten().max(), Some(&500));
+    }
+}
@@ -0,0 +1,88 @@
+/*
+  Copyright 2024 Marat Bukharov
+
+  Licensed under the Apache License, Version 2.0 (the "License");
+  you may not use this file except in compliance with the License.
+  You may obtain a copy of the License at
+
+      http://www.apache.org/licenses/LICENSE-2.0
+
+  Unless required by applicable law or agreed to in writing, software
+  distributed under the License is distributed on an "AS IS" BASIS,
+  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+  See the License for the specific language governing permissions and
+  limitations under the License.
+*/
+
+use generator::rng;
+use std::env;
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: generator DAY SIZE... [--seed SEED]
+
+  01 PAIRS
+  02 REPORTS LEVELS
+  03 BYTES
+  04 HEIGHT WIDTH
+  05 PAGES UPDATES
+  06 HEIGHT WIDTH
+  07 EQUATIONS OPERANDS
+  08 HEIGHT WIDTH
+  09 DIGITS";
+
+fn generate(day: &str, sizes: &[usize], seed: u64, out: &mut impl Write) -> Option<io::Result<()>> {
+    let rng = &mut rng(seed);
+    Some(match (day, sizes) {
+        ("01", &[pairs]) => generator::day01::generate(out, rng, pairs),
+        ("02", &[reports, levels]) => generator::day02::generate(out, rng, reports, levels),
+        ("03", &[bytes]) => generator::day03::generate(out, rng, bytes),
+        ("04", &[height, width]) => generator::day04::generate(out, rng, height, width),
+        ("05", &[pages, updates]) => generator::day05::generate(out, rng, pages, updates),
+        ("06", &[height, width]) => generator::day06::generate(out, rng, height, width),
+        ("07", &[equations, operands]) => generator::day07::generate(out, rng, equations, operands),
+        ("08", &[height, width]) => generator::day08::generate(out, rng, height, width),
+        ("09", &[digits]) => generator::day09::generate(out, rng, digits),
+        _ => return None,
+    })
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<_> = env::args().skip(1).collect();
+    let seed = match args.iter().position(|arg| arg == "--seed") {
+        Some(pos) => match args.drain(pos..).nth(1).and_then(|seed| seed.parse().ok()) {
+            Some(seed) => seed,
+            None => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => 0,
+    };
+    let Some((day, sizes)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+    let Ok(sizes) = sizes
+        .iter()
+        .map(|size| size.parse())
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    match generate(day, &sizes, seed, &mut out).map(|result| result.and_then(|_| out.flush())) {
+        Some(Ok(())) => ExitCode::SUCCESS,
+        Some(Err(err)) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+        None => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}